@@ -1,17 +1,22 @@
 use crate::{
-    config::{OPTIONS, options},
-    entry::Entry,
+    cli::CLAP_ARGS,
+    config::{self, OPTIONS, options},
+    entry::{Entry, Metadata},
     event::Event,
+    event_log::EventLog,
+    filter::Filter,
     fuzzy_finder::{
         FuzzyFinder,
-        picker::{Global, Local},
+        picker::{Content, GameList, Global, Local, ProfileList},
     },
     game::{
-        Games,
+        self, Game, Games,
         creation::{CreatingGame, Step},
         profile::Profile,
+        state::PopupSizes,
     },
-    help::Help,
+    hash_cache::HashCache,
+    help::{Bindings, Help},
     input::{self, Input, Mode},
     message::{Message, set_msg_if_error},
     search::Search,
@@ -29,8 +34,26 @@ use anyhow::{Context, Result, ensure};
 use crossterm::event::{Event as CrosstermEvent, EventStream};
 use futures::StreamExt;
 use ratatui::widgets::ListState;
-use std::path::{Path, PathBuf};
-use tokio::sync::mpsc::{self, UnboundedReceiver};
+use std::{
+    collections::{HashMap, HashSet},
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime},
+};
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+
+/// Number of files above which copying is done in the background.
+const BACKGROUND_COPY_THRESHOLD: usize = 20;
+
+/// Number of rotating slots the live save is copied into by the autosave.
+const AUTOSAVE_SLOTS: usize = 5;
+
+/// Time within which the delete key has to be pressed again if `delete_requires_double_press`
+/// is set.
+const DOUBLE_PRESS_WINDOW: Duration = Duration::from_secs(2);
 
 pub struct App {
     pub games: Games,
@@ -43,7 +66,56 @@ pub struct App {
     pub fuzzy_finder: FuzzyFinder,
     pub game_creation: CreatingGame,
     pub watcher: Watcher,
+    pub flat_view: bool,
+    pub filter: Filter,
+    pub event_log: EventLog,
+    hash_cache: Arc<Mutex<HashCache>>,
+    /// Saves with the same contents as the active save, which are only looked up again once the
+    /// active save or the entries change.
+    pub identical: HashSet<NodeId>,
+    /// Profile and active save that the identical saves were looked up for.
+    identical_of: Option<(PathBuf, NodeId)>,
+    /// Whether the entries changed since the identical saves were looked up.
+    identical_stale: bool,
+    /// Number of the latest lookup of the identical saves, whose result is the only one applied.
+    identical_lookup: u64,
+    /// Whether the commands that modify the save files are disabled.
+    pub read_only: bool,
+    /// Whether the paths in the messages, prompts and the breadcrumb are absolute instead of
+    /// relative to the profile.
+    pub absolute_paths: bool,
+    /// Whether the live save is backed up before loading even if the `backup_before_load` option
+    /// is disabled.
+    pub backup_on_load: bool,
+    /// Number typed before a command in the tree, which makes the commands jumping to the first
+    /// or the last line jump to that line instead.
+    pub count: Option<usize>,
     pending_move: Option<HandleMove>,
+    pub pending_renames: Vec<(PathBuf, PathBuf)>,
+    /// Folders without any files to delete once it is confirmed, in tree order.
+    pub pending_prunes: Vec<PathBuf>,
+    /// Moves of the marked entries shown while waiting for the confirmation, with the
+    /// `confirm_move` option.
+    pub pending_moves: Vec<(PathBuf, PathBuf)>,
+    /// Entries yanked or cut to be pasted into another folder.
+    pub clipboard: Option<Clipboard>,
+    /// Existing file to overwrite by importing once it is confirmed.
+    pub pending_import: Option<PathBuf>,
+    /// Imported save to select and mark as active once it shows up in the tree, with the
+    /// `mark_import_active` option.
+    pending_activation: Option<PathBuf>,
+    /// Created folder to select once it shows up in the tree.
+    pending_selection: Option<PathBuf>,
+    /// External program to run with the terminal handed over to it.
+    pub external_command: Option<ExternalCommand>,
+    /// Modification time of the live save when it was last autosaved.
+    last_autosave: Option<SystemTime>,
+    /// Task that sends the autosave events, if the `autosave_interval` option is set.
+    autosave_task: Option<JoinHandle<()>>,
+    /// When the delete key was first pressed and the entry selected then, if it is waiting for
+    /// the second press.
+    pending_delete: Option<(Instant, Option<NodeId>)>,
+    tx: UnboundedSender<Event>,
     rx: UnboundedReceiver<Event>,
 }
 
@@ -60,15 +132,51 @@ impl App {
             search: Search::default(),
             fuzzy_finder: FuzzyFinder::default(),
             game_creation: CreatingGame::default(),
-            watcher: Watcher::new(tx)?,
+            watcher: Watcher::new(tx.clone())?,
+            flat_view: false,
+            filter: Filter::default(),
+            event_log: EventLog::default(),
+            hash_cache: Arc::default(),
+            identical: HashSet::new(),
+            identical_of: None,
+            identical_stale: false,
+            identical_lookup: 0,
+            read_only: CLAP_ARGS.get_flag("read_only"),
+            absolute_paths: false,
+            backup_on_load: false,
+            count: None,
             pending_move: None,
+            pending_renames: Vec::new(),
+            pending_prunes: Vec::new(),
+            pending_moves: Vec::new(),
+            clipboard: None,
+            pending_import: None,
+            pending_activation: None,
+            pending_selection: None,
+            external_command: None,
+            last_autosave: None,
+            autosave_task: None,
+            pending_delete: None,
+            tx,
             rx,
         };
 
         if app.games.get_profile().is_some() {
             app.setup_state();
-        } else {
-            app.open_game_window();
+        }
+
+        if OPTIONS.get().remember_popup_sizes {
+            let sizes = PopupSizes::read();
+            app.help.size = sizes.help;
+            app.fuzzy_finder.size = sizes.fuzzy_finder;
+        }
+
+        match OPTIONS.get().start_view {
+            options::StartView::Tree if app.games.get_profile().is_some() => (),
+            options::StartView::Profiles if app.games.get_game().is_some() => {
+                app.open_profile_window();
+            }
+            _ => app.open_game_window(),
         }
 
         Ok(app)
@@ -80,6 +188,7 @@ impl App {
 
         self.auto_mark_save_file();
         self.watcher.watch_non_recursive(&utils::get_state_dir()?);
+        self.schedule_autosave();
 
         if let Some(game) = self.games.get_game() {
             self.watcher.watch_non_recursive(&game.path);
@@ -90,6 +199,11 @@ impl App {
         }
 
         loop {
+            if self.message.take_bell() {
+                ui::bell();
+            }
+
+            self.update_identical();
             terminal.draw(|f| ui::draw(f, &mut self))?;
 
             let event = tokio::select! {
@@ -104,24 +218,90 @@ impl App {
                     {
                         break;
                     }
+
+                    if let Some(command) = self.external_command.take() {
+                        // the event stream would compete with the program for the input
+                        drop(term_events);
+                        let after = command.after;
+                        let res = ui::suspend(&mut terminal, || command.run());
+                        term_events = EventStream::new();
+
+                        match res {
+                            Ok(success) => {
+                                if let Some(after) = after.filter(|_| success) {
+                                    after(&mut self);
+                                }
+                            }
+                            Err(e) => self.message.set_error(&e),
+                        }
+                    }
                 }
                 Event::FileSystem(event) => {
                     let Some(event) = self.watcher.handle_event(event) else {
                         continue;
                     };
+                    self.identical_stale = true;
 
                     let res = match event.context {
                         EventContext::Game => self.on_game_event(&event),
                         EventContext::Profile => self.on_profile_event(&event),
                         EventContext::Entry => self.handle_file_system_event(&event),
                     };
+                    self.event_log.push(&event, &res);
+
+                    // the filter only depends on the names of the entries
+                    if self.filter.is_active() && !matches!(event.kind, EventKind::Modify) {
+                        self.apply_filter();
+                    }
 
                     set_msg_if_error!(self.message, res);
                 }
                 Event::ClearMessage => self.message.clear(),
+                Event::Progress { done, total } => {
+                    self.message.set_info(&format!("Copied {done}/{total}"));
+                }
+                Event::Autosave => {
+                    let res = self.autosave();
+                    set_msg_if_error!(self.message, res);
+                }
+                Event::IdenticalSaves { lookup, ids } => {
+                    // the entries or the active save changed if there was a later lookup
+                    if lookup == self.identical_lookup {
+                        self.identical = ids;
+                    }
+                }
+                Event::TaskFinished(res) => match res {
+                    Ok(()) => {
+                        self.message.set_message_with_timeout("Copy completed", 5);
+                        self.message.ring_bell();
+                    }
+                    Err(e) => self.message.set_error(&e),
+                },
             }
         }
 
+        self.flush_state()
+            .context("Couldn't save the state on quit")
+    }
+
+    /// Writes the state of the active game and profile once more, so that a write that failed
+    /// while running doesn't leave stale state files for the next launch.
+    fn flush_state(&self) -> Result<()> {
+        let Some(game) = self.games.get_game() else {
+            return Ok(());
+        };
+
+        game::update_active_game(&game.name())?;
+        game.write_state()?;
+
+        // the state of a profile whose entries aren't loaded would lose them
+        if let Some(profile) = game
+            .get_profile()
+            .filter(|profile| profile.entries.root().is_some())
+        {
+            profile.write_state()?;
+        }
+
         Ok(())
     }
 
@@ -167,8 +347,14 @@ impl App {
         let profile = self.games.get_profile_mut();
         let active_path = profile.as_deref().and_then(Profile::get_active_save_file);
 
+        self.filter.clear();
+
         if let Some(entries) = profile.map(|profile| &mut profile.entries) {
-            self.tree_state = TreeState::default();
+            self.tree_state = TreeState {
+                root_visible: OPTIONS.get().show_root,
+                compact_folders: OPTIONS.get().compact_folders,
+                ..TreeState::default()
+            };
 
             for id in entries.iter_ids() {
                 let node = &mut entries[id];
@@ -198,6 +384,29 @@ impl App {
         self.mode = Mode::ProfileSelection;
     }
 
+    /// Closes the game or the profile selection window, keeping the current selection. Warns if
+    /// there is no selection to fall back to, or if there is nothing that could be selected.
+    pub fn close_selection_window(&mut self) {
+        match self.mode {
+            Mode::GameSelection => match self.games.get_game() {
+                Some(game) if game.get_profile().is_some() => self.mode = Mode::Normal,
+                Some(_) => self.open_profile_window(),
+                None if self.games.inner.items.is_empty() => {
+                    self.message.set_warning("Create a game to continue");
+                }
+                None => self.message.set_warning("Select a game to continue"),
+            },
+            Mode::ProfileSelection => match self.games.get_game() {
+                Some(game) if game.get_profile().is_some() => self.mode = Mode::Normal,
+                Some(game) if game.profiles.items.is_empty() => {
+                    self.message.set_warning("Create a profile to continue");
+                }
+                _ => self.message.set_warning("Select a profile to continue"),
+            },
+            _ => (),
+        }
+    }
+
     pub fn confirm_game_selection(&mut self) {
         let previous_game_path = self.games.get_game().map(|profile| profile.path.clone());
         let previous_profile_path = self.games.get_profile().map(|profile| profile.path.clone());
@@ -239,8 +448,8 @@ impl App {
                 state.step = Step::PresetOrManual(false);
             }
             Step::EnterPath => {
-                let path = PathBuf::from(input.unwrap());
-                Games::create_game(&mut self.games, state.name.as_ref().unwrap(), &path)?;
+                let paths = utils::split_paths(&input.unwrap());
+                Games::create_game(&mut self.games, state.name.as_ref().unwrap(), paths)?;
                 self.mode.select_previous();
             }
             _ => unreachable!(),
@@ -264,7 +473,45 @@ impl App {
         }
     }
 
+    /// Switches to the next or the previous game, wrapping around at the ends.
+    pub fn cycle_game(&mut self, forward: bool) {
+        let Some(active) = self.games.active_game else {
+            return;
+        };
+
+        let games = &mut self.games.inner;
+        if games.items.len() < 2 {
+            return;
+        }
+
+        games
+            .state
+            .select(Some(cycle_index(active, games.items.len(), forward)));
+        self.confirm_game_selection();
+    }
+
+    /// Switches to the next or the previous profile of the game, wrapping around at the ends.
+    pub fn cycle_profile(&mut self, forward: bool) {
+        let Some(game) = self.games.get_game_mut() else {
+            return;
+        };
+
+        let (Some(active), len) = (game.active_profile, game.profiles.items.len()) else {
+            return;
+        };
+
+        if len < 2 {
+            return;
+        }
+
+        game.profiles
+            .state
+            .select(Some(cycle_index(active, len, forward)));
+        self.confirm_profile_selection();
+    }
+
     pub fn on_profile_change(&mut self, previous_profile_path: Option<PathBuf>) {
+        self.identical_stale = true;
         self.setup_state();
         self.auto_mark_save_file();
         self.watcher
@@ -281,6 +528,15 @@ impl App {
         let res = match self.mode.confirmation_context() {
             ConfirmationContext::Deletion => self.delete_selected_entry(),
             ConfirmationContext::Replacing => self.replace_save_file(),
+            ConfirmationContext::ReplacingWithActive => self.replace_with_active_save_file(),
+            ConfirmationContext::BulkRename => self.bulk_rename(),
+            ConfirmationContext::Pruning => self.prune_empty_folders(),
+            ConfirmationContext::Move { top_level } => {
+                self.pending_moves.clear();
+                self.move_marked_entries(top_level);
+                Ok(())
+            }
+            ConfirmationContext::ImportOverwrite => self.confirm_import_overwrite(),
             ConfirmationContext::GameDeletion => self.games.delete_selected_game(),
             ConfirmationContext::ProfileDeletion => self
                 .games
@@ -295,9 +551,27 @@ impl App {
 
     pub fn prompt_for_confirmation(&mut self, context: ConfirmationContext) {
         match context {
-            ConfirmationContext::Deletion if self.tree_state.selected.is_none() => {}
-            ConfirmationContext::Replacing if matches!(self.selected_entry(), Some(entry) if entry.is_folder()) =>
+            ConfirmationContext::Deletion if self.selected_id().is_none() => {}
+            ConfirmationContext::Replacing if matches!(self.selected_entry(), Some(entry) if !self.is_save_entry(entry)) =>
                 {}
+            ConfirmationContext::ReplacingWithActive => {
+                let active_path = self
+                    .games
+                    .get_profile()
+                    .and_then(Profile::get_active_save_file);
+
+                match (self.selected_entry(), active_path) {
+                    (Some(entry), Some(active_path))
+                        if self.is_save_entry(entry) && entry.path != active_path =>
+                    {
+                        self.mode = Mode::Confirmation(Prompt::new(self, context));
+                    }
+                    (Some(_), None) => self
+                        .message
+                        .set_warning("No save file is marked as active."),
+                    _ => {}
+                }
+            }
             ConfirmationContext::GameDeletion if self.games.inner.state.selected().is_none() => {}
             ConfirmationContext::ProfileDeletion
                 if self.games.get_profiles().state.selected().is_none() => {}
@@ -305,22 +579,73 @@ impl App {
         }
     }
 
+    /// Returns the id of the selected entry unless the profile root is selected.
+    fn selected_id(&self) -> Option<NodeId> {
+        self.tree_state.selected.filter(|id| *id != NodeId::root())
+    }
+
     pub fn selected_entry(&self) -> Option<&Node<Entry>> {
-        self.tree_state
-            .selected
+        self.selected_id()
             .and_then(|id| self.games.get_entries().and_then(|entries| entries.get(id)))
     }
 
     pub fn selected_entry_mut(&mut self) -> Option<&mut Node<Entry>> {
-        self.tree_state.selected.and_then(|id| {
+        self.selected_id().and_then(|id| {
             self.games
                 .get_entries_mut()
                 .and_then(|entries| entries.get_mut(id))
         })
     }
 
+    /// Unmarks the entries that are no longer part of the tree, e.g. because they were deleted
+    /// externally, and warns about them.
+    fn unmark_stale_entries(&mut self) {
+        let Some(entries) = self.games.get_entries() else {
+            return;
+        };
+
+        let marked_count = self.tree_state.marked.len();
+        self.tree_state
+            .marked
+            .retain(|id| entries.get(*id).is_some() && !entries.detached_from_root(*id));
+
+        let stale_count = marked_count - self.tree_state.marked.len();
+        if stale_count > 0 {
+            self.message.set_warning(&format!(
+                "Skipped {stale_count} marked entries that no longer exist."
+            ));
+        }
+    }
+
+    /// Deletes the selected or the marked entries if the delete key was pressed for the same
+    /// selection within `DOUBLE_PRESS_WINDOW`, asks for the second press otherwise.
+    pub fn delete_on_double_press(&mut self) {
+        if self.selected_id().is_none() && self.tree_state.marked.is_empty() {
+            return;
+        }
+
+        let selected = self.tree_state.selected;
+
+        match self.pending_delete.take() {
+            Some((pressed_at, pending))
+                if pending == selected && pressed_at.elapsed() < DOUBLE_PRESS_WINDOW =>
+            {
+                self.message.clear();
+                set_msg_if_error!(self.message, self.delete_selected_entry());
+            }
+            _ => {
+                self.pending_delete = Some((Instant::now(), selected));
+                self.message.set_message_with_timeout(
+                    "Press again to delete",
+                    DOUBLE_PRESS_WINDOW.as_secs(),
+                );
+            }
+        }
+    }
+
     pub fn delete_selected_entry(&mut self) -> Result<()> {
         if !self.tree_state.marked.is_empty() {
+            self.unmark_stale_entries();
             let entries = self.games.get_entries().unwrap();
             for id in self.tree_state.marked.drain() {
                 entries[id].delete()?;
@@ -332,6 +657,29 @@ impl App {
         Ok(())
     }
 
+    /// Returns the entry to select after the selected entry at `path` is deleted according to the
+    /// `after_delete` option, or `None` to select the previous entry.
+    fn selection_after_delete(&self, path: &Path) -> Option<NodeId> {
+        let entries = self.games.get_entries()?;
+        let id = (entries.find_by_path(path)).filter(|id| self.tree_state.selected == Some(*id))?;
+
+        match OPTIONS.get().after_delete {
+            options::AfterDelete::SelectPrev => None,
+            options::AfterDelete::SelectNext => {
+                let ids = self.visible_ids();
+                let idx = ids.iter().position(|visible| *visible == id)?;
+
+                // skip the contents of the deleted folder
+                ids[idx + 1..]
+                    .iter()
+                    .copied()
+                    .find(|next| !entries.ancestors(*next).any(|ancestor| ancestor == id))
+            }
+            options::AfterDelete::Stay if self.in_list_view() => None,
+            options::AfterDelete::Stay => self.tree_state.parent_of(entries, id),
+        }
+    }
+
     pub fn take_input(&mut self, mode: Mode) {
         self.footer_input = Some(Input::from(&mode));
         self.mode = mode;
@@ -376,6 +724,7 @@ impl App {
         utils::check_for_dup(&path)?;
         std::fs::create_dir(&path)?;
         node.expanded = Some(true);
+        self.pending_selection = Some(path);
 
         Ok(())
     }
@@ -387,7 +736,7 @@ impl App {
 
         let mut file_name = entry.name().to_string_lossy().into_owned();
 
-        if let Some(empty_opt) = &OPTIONS.rename.empty {
+        if let Some(empty_opt) = &OPTIONS.get().rename.empty {
             if let options::RenameEmpty::All = empty_opt {
                 file_name = String::new();
             } else if let Some(dot_idx) = file_name.rfind('.') {
@@ -404,7 +753,7 @@ impl App {
         let input = self.footer_input.as_mut().unwrap();
         input.set_text(&file_name);
 
-        match OPTIONS.rename.cursor {
+        match OPTIONS.get().rename.cursor {
             options::RenameCursor::End => (),
             options::RenameCursor::Start => input.set_idx(0),
             options::RenameCursor::BeforeExt => {
@@ -430,7 +779,303 @@ impl App {
         utils::rename(old_path, &new_path)
     }
 
+    /// Removes the extension of the `toggled_extension` option from the name of the selected
+    /// entry if it has it, appends it otherwise.
+    pub fn toggle_extension(&mut self) -> Result<()> {
+        let options = OPTIONS.get();
+        let ext = options.toggled_extension.trim_start_matches('.');
+        ensure!(!ext.is_empty(), "The toggled_extension option is empty.");
+
+        let Some(entry) = self.selected_entry() else {
+            return Ok(());
+        };
+
+        let name = entry.name().to_string_lossy();
+        let suffix = format!(".{ext}");
+        let new_name = match name.strip_suffix(&suffix) {
+            Some(stem) if !stem.is_empty() => stem.to_owned(),
+            _ => format!("{name}{suffix}"),
+        };
+
+        utils::rename(&entry.path, &entry.path.with_file_name(new_name))
+    }
+
+    pub fn enter_bulk_renaming(&mut self) {
+        if self.tree_state.marked.is_empty() {
+            self.message.set_warning("No entries are marked.");
+            return;
+        }
+
+        self.take_input(Mode::BulkRenaming);
+        self.footer_input.as_mut().unwrap().set_text("{name} {n}");
+    }
+
+    /// Expands the entered template for every marked entry and asks for confirmation before
+    /// renaming them. `{n}` is replaced with a counter and `{name}` with the original name
+    /// without its extension. Extensions of files are kept.
+    pub fn prepare_bulk_rename(&mut self) -> Result<()> {
+        let template = self.extract_input();
+        self.unmark_stale_entries();
+        ensure!(!self.tree_state.marked.is_empty(), "No entries are marked.");
+        ensure!(!template.trim().is_empty(), "Template can't be empty.");
+        ensure!(
+            template.contains("{n}") || self.tree_state.marked.len() == 1,
+            "Template must contain {{n}} when renaming multiple entries."
+        );
+        ensure!(
+            !template.contains(std::path::is_separator),
+            "Template can't contain path separators."
+        );
+
+        let entries = self.games.get_entries().unwrap();
+
+        self.pending_renames = entries
+            .iter_ids()
+            .filter(|id| self.tree_state.marked.contains(id))
+            .enumerate()
+            .map(|(idx, id)| {
+                let path = &entries[id].path;
+                let stem = path.file_stem().unwrap().to_string_lossy();
+                let mut name = template
+                    .replace("{n}", &(idx + 1).to_string())
+                    .replace("{name}", &stem);
+
+                if entries[id].is_file()
+                    && let Some(ext) = path.extension()
+                {
+                    name = format!("{name}.{}", ext.to_string_lossy());
+                }
+
+                (path.clone(), path.with_file_name(name))
+            })
+            .filter(|(from, to)| from != to)
+            .collect();
+
+        if self.pending_renames.is_empty() {
+            self.message.set_warning("Nothing to rename.");
+        } else {
+            self.prompt_for_confirmation(ConfirmationContext::BulkRename);
+        }
+
+        Ok(())
+    }
+
+    /// Renames the entries in `pending_renames`. They are renamed in reverse tree order so that
+    /// the entries inside a renamed folder are renamed before the paths to them change.
+    pub fn bulk_rename(&mut self) -> Result<()> {
+        let mut failed = 0;
+
+        for (from, to) in std::mem::take(&mut self.pending_renames).iter().rev() {
+            if utils::rename(from, to).is_err() {
+                failed += 1;
+            }
+        }
+
+        self.tree_state.marked.clear();
+
+        ensure!(failed == 0, "Couldn't rename {failed} of the entries.");
+
+        Ok(())
+    }
+
+    pub fn start_pruning(&mut self) {
+        let Some(entries) = self.games.get_entries() else {
+            return;
+        };
+
+        self.pending_prunes = entries
+            .descendants(NodeId::root())
+            .skip(1)
+            .filter(|id| entries.descendants(*id).all(|id| entries[id].is_folder()))
+            .map(|id| entries[id].path.clone())
+            .collect();
+
+        if self.pending_prunes.is_empty() {
+            self.message.set_warning("No empty folders.");
+        } else {
+            self.prompt_for_confirmation(ConfirmationContext::Pruning);
+        }
+    }
+
+    /// Deletes the folders in `pending_prunes`. The nested folders are deleted before the folders
+    /// containing them, and folders that turn out to have contents are kept.
+    pub fn prune_empty_folders(&mut self) -> Result<()> {
+        let mut failed = 0;
+
+        for path in std::mem::take(&mut self.pending_prunes).iter().rev() {
+            let res = std::fs::remove_dir(path);
+            utils::log("prune", &[path], &res);
+
+            if res.is_err() {
+                failed += 1;
+            }
+        }
+
+        ensure!(failed == 0, "Couldn't delete {failed} of the folders.");
+
+        Ok(())
+    }
+
+    /// Puts the marked entries, or the selected one if none are marked, into the clipboard to be
+    /// copied, or moved if `cut` is set, by pasting.
+    pub fn yank_entries(&mut self, cut: bool) {
+        self.unmark_stale_entries();
+
+        let ids = if self.tree_state.marked.is_empty() {
+            self.selected_id().into_iter().collect()
+        } else {
+            self.marked_in_tree_order()
+        };
+
+        let Some(entries) = self.games.get_entries().filter(|_| !ids.is_empty()) else {
+            return;
+        };
+
+        let paths: Vec<PathBuf> = ids.iter().map(|id| entries[*id].path.clone()).collect();
+        let count = paths.len();
+
+        self.clipboard = Some(Clipboard { paths, cut });
+        self.tree_state.marked.clear();
+        self.message.set_info(&format!(
+            "{} {count} entr{}",
+            if cut { "Cut" } else { "Yanked" },
+            if count == 1 { "y" } else { "ies" }
+        ));
+    }
+
+    /// Pastes the entries in the clipboard into the context of the selected entry. Copies get a
+    /// new name if the name is taken, while cut entries are moved only if it isn't. The clipboard
+    /// is emptied after pasting cut entries. Large folders are copied on a background task.
+    pub fn paste_entries(&mut self) -> Result<()> {
+        let Some(clipboard) = &self.clipboard else {
+            self.message.set_warning("Nothing is yanked.");
+            return Ok(());
+        };
+
+        let context_id = self.context_node_id(false);
+        let entries = self.games.get_entries_mut().unwrap();
+        let base_path = entries[context_id].path.clone();
+        let mut failed = 0;
+        let mut background_copies = Vec::new();
+
+        for path in &clipboard.paths {
+            let mut new_path = base_path.join(path.file_name().unwrap());
+
+            if *path == new_path && clipboard.cut {
+                continue;
+            }
+
+            let large = || utils::count_files(path) > BACKGROUND_COPY_THRESHOLD;
+            let queued = background_copies.len();
+
+            // a folder can't be pasted into itself
+            let res = if base_path.starts_with(path) {
+                Err(anyhow::anyhow!("Can't paste a folder into itself."))
+            } else if clipboard.cut {
+                utils::check_for_dup(&new_path).and_then(|()| {
+                    match std::fs::rename(path, &new_path) {
+                        Err(e) if utils::crosses_devices(&e) && large() => {
+                            background_copies.push((path.clone(), new_path.clone()));
+                            Ok(())
+                        }
+                        Err(e) if utils::crosses_devices(&e) => {
+                            utils::copy_and_remove(path, &new_path, &mut || ())
+                        }
+                        res => Ok(res?),
+                    }
+                })
+            } else {
+                utils::validate_name(&mut new_path);
+
+                if large() {
+                    background_copies.push((path.clone(), new_path.clone()));
+                    Ok(())
+                } else {
+                    utils::copy_recursive(path, &new_path, &mut || ())
+                }
+            };
+
+            // the background task logs the copies it makes
+            if background_copies.len() > queued {
+                continue;
+            }
+
+            let operation = if clipboard.cut { "move" } else { "copy" };
+            utils::log(operation, &[path, &new_path], &res);
+
+            if res.is_err() {
+                failed += 1;
+            }
+        }
+
+        entries[context_id].expanded = Some(true);
+
+        if !background_copies.is_empty() {
+            self.copy_in_background(background_copies, clipboard.cut);
+        }
+
+        if clipboard.cut {
+            self.clipboard = None;
+        }
+
+        ensure!(failed == 0, "Couldn't paste {failed} of the entries.");
+
+        Ok(())
+    }
+
+    /// Moves the marked entries into the context of the selected entry, or asks for confirmation
+    /// first with the `confirm_move` option if any of them changes folders.
     pub fn move_entries(&mut self, top_level: bool) {
+        if !OPTIONS.get().confirm_move {
+            self.move_marked_entries(top_level);
+            return;
+        }
+
+        self.unmark_stale_entries();
+
+        let Some(selected) = self.tree_state.selected else {
+            return;
+        };
+
+        let top_level = top_level && selected != NodeId::root();
+        let context_id = self.context_node_id(top_level);
+        let entries = self.games.get_entries().unwrap();
+        let base_path = &entries[context_id].path;
+
+        self.pending_moves = (self.marked_in_tree_order().into_iter())
+            .map(|id| {
+                let path = &entries[id].path;
+                (path.clone(), base_path.join(path.file_name().unwrap()))
+            })
+            .filter(|(from, to)| from != to)
+            .collect();
+
+        if self.pending_moves.is_empty() {
+            self.move_marked_entries(top_level);
+        } else {
+            self.prompt_for_confirmation(ConfirmationContext::Move { top_level });
+        }
+    }
+
+    /// Returns the marked entries in the order they are in the tree.
+    fn marked_in_tree_order(&self) -> Vec<NodeId> {
+        let Some(entries) = self.games.get_entries() else {
+            return Vec::new();
+        };
+
+        let mut marked: Vec<NodeId> = self.tree_state.marked.iter().copied().collect();
+        let order: HashMap<NodeId, usize> = (entries.descendants(NodeId::root()))
+            .enumerate()
+            .map(|(idx, id)| (id, idx))
+            .collect();
+        marked.sort_by_key(|id| order.get(id));
+
+        marked
+    }
+
+    fn move_marked_entries(&mut self, top_level: bool) {
+        self.unmark_stale_entries();
+
         if self.tree_state.marked.is_empty() {
             return;
         }
@@ -439,36 +1084,64 @@ impl App {
             return;
         };
 
+        // the root is its own context, so moving to the top level is the same as moving into it
+        let top_level = top_level && selected != NodeId::root();
         let context_id = self.context_node_id(top_level);
         let mut moved_outside: u32 = 0;
         let mut moved_in = false;
         let mut fail = false;
+        let mut copied = false;
+        let mut background_copies = Vec::new();
+
+        // process the marked entries in tree order so that their order is kept at the destination
+        let marked = self.marked_in_tree_order();
+        self.tree_state.marked.clear();
 
         let profile = self.games.get_profile_mut().unwrap();
         let entries = &mut profile.entries;
         let base_path = entries[context_id].path.clone();
 
-        for id in self.tree_state.marked.drain() {
+        let mut last_moved_in = None;
+
+        for id in marked {
             let entry = &entries[id];
             let new_path = base_path.join(entry.name());
 
             if entry.path == new_path {
                 moved_in = true;
-                if entries[selected].is_folder() && !top_level {
+                if let Some(last) = last_moved_in {
+                    entries.move_entry(Tree::insert_after, last, id);
+                } else if entries[selected].is_folder() && !top_level {
                     entries.move_entry(Tree::prepend, selected, id);
                 } else {
                     entries.move_entry(Tree::insert_after, selected, id);
                 }
-            } else if utils::check_for_dup(&new_path).is_err()
-                || std::fs::rename(&entry.path, new_path).is_err()
-            {
+                last_moved_in = Some(id);
+            } else if utils::check_for_dup(&new_path).is_err() {
                 fail = true;
             } else {
-                moved_outside += 1;
+                let res = std::fs::rename(&entry.path, &new_path);
+                utils::log("move", &[&entry.path, &new_path], &res);
+
+                match res {
+                    Ok(()) => moved_outside += 1,
+                    Err(e) if utils::crosses_devices(&e) => {
+                        if utils::count_files(&entry.path) > BACKGROUND_COPY_THRESHOLD {
+                            background_copies.push((entry.path.clone(), new_path));
+                        } else if utils::copy_and_remove(&entry.path, &new_path, &mut || ())
+                            .is_err()
+                        {
+                            fail = true;
+                        } else {
+                            copied = true;
+                        }
+                    }
+                    Err(_) => fail = true,
+                }
             }
         }
 
-        if moved_in || moved_outside > 0 {
+        if moved_in || moved_outside > 0 || copied || !background_copies.is_empty() {
             entries[context_id].expanded = Some(true);
         }
 
@@ -504,11 +1177,138 @@ impl App {
             self.message
                 .set_error_from_str("Couldn't move some of the files");
         }
+
+        if !background_copies.is_empty() {
+            self.copy_in_background(background_copies, true);
+        }
+    }
+
+    /// Moves the contents of the marked folder into the selected folder and removes the marked
+    /// folder once it is empty. Entries whose names are already taken are renamed with a suffix.
+    pub fn merge_folders(&mut self) -> Result<()> {
+        self.unmark_stale_entries();
+
+        let Some(entries) = self.games.get_entries() else {
+            return Ok(());
+        };
+
+        let (Some(source), Some(target)) = (
+            self.tree_state.marked.iter().next().copied(),
+            self.tree_state.selected,
+        ) else {
+            self.message
+                .set_warning("Mark a folder to merge into the selected folder.");
+            return Ok(());
+        };
+
+        ensure!(
+            self.tree_state.marked.len() == 1,
+            "Only one folder can be merged at a time."
+        );
+        ensure!(
+            entries[source].is_folder() && entries[target].is_folder(),
+            "Only folders can be merged."
+        );
+
+        let source_path = entries[source].path.clone();
+        let target_path = entries[target].path.clone();
+
+        ensure!(
+            !target_path.starts_with(&source_path),
+            "A folder can't be merged into itself."
+        );
+
+        let mut moved = Vec::new();
+        let mut renamed = 0;
+        let mut failed = 0;
+
+        for child in entries.children(source) {
+            let path = &entries[child].path;
+            let mut new_path = target_path.join(entries[child].name());
+            utils::validate_name(&mut new_path);
+
+            let res = std::fs::rename(path, &new_path);
+            utils::log("move", &[path, &new_path], &res);
+
+            if res.is_err() {
+                failed += 1;
+                continue;
+            }
+
+            if new_path.file_name() != Some(entries[child].name()) {
+                renamed += 1;
+            }
+
+            moved.push((child, path.clone(), new_path));
+        }
+
+        // the tree is updated here instead of by the file system events so that the merged
+        // entries keep their order
+        let profile = self.games.get_profile_mut().unwrap();
+
+        for (id, path, new_path) in &moved {
+            if failed > 0 {
+                profile.entries.move_entry(Tree::append, target, *id);
+            }
+
+            profile.entries.update_paths(*id, new_path)?;
+            profile.rename_pinned(path, new_path);
+            profile.rename_active_save_file(path, new_path)?;
+        }
+
+        profile.write_state()?;
+        self.tree_state.marked.clear();
+        self.tree_state.select_unchecked(Some(target));
+
+        ensure!(
+            failed == 0,
+            "Couldn't move {failed} of the entries, so the marked folder is kept."
+        );
+
+        profile.entries.move_children(source, target);
+
+        let res = std::fs::remove_dir(&source_path);
+        utils::log("delete", &[&source_path], &res);
+        res?;
+
+        if renamed > 0 {
+            self.message.set_info(&format!(
+                "Renamed {renamed} entries whose names were already taken."
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Copies the given paths on a background task, removing the sources afterwards if `remove`
+    /// is set, and reports the progress through the event channel.
+    fn copy_in_background(&self, paths: Vec<(PathBuf, PathBuf)>, remove: bool) {
+        let tx = self.tx.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let total = paths.iter().map(|(from, _)| utils::count_files(from)).sum();
+            let mut done = 0;
+            let mut on_copy = || {
+                done += 1;
+                let _ = tx.send(Event::Progress { done, total });
+            };
+
+            let res = paths.iter().try_for_each(|(from, to)| {
+                let res = if remove {
+                    utils::copy_and_remove(from, to, &mut on_copy)
+                } else {
+                    utils::copy_recursive(from, to, &mut on_copy)
+                };
+                utils::log("copy", &[from, to], &res);
+                res
+            });
+
+            let _ = tx.send(Event::TaskFinished(res));
+        });
     }
 
     pub fn move_up(&mut self) {
-        let Some((id, profile)) = (self.tree_state.selected).zip(self.games.get_profile_mut())
-        else {
+        let Some((id, profile)) = self.selected_id().zip(self.games.get_profile_mut()) else {
             return;
         };
 
@@ -526,8 +1326,7 @@ impl App {
     }
 
     pub fn move_down(&mut self) {
-        let Some((id, profile)) = (self.tree_state.selected).zip(self.games.get_profile_mut())
-        else {
+        let Some((id, profile)) = self.selected_id().zip(self.games.get_profile_mut()) else {
             return;
         };
 
@@ -544,17 +1343,47 @@ impl App {
         set_msg_if_error!(self.message, profile.write_state());
     }
 
-    pub fn open_all_folds(&mut self) {
-        if let Some(entries) = self.games.get_entries_mut() {
+    /// Opens the selected folder and its direct children, leaving the deeper folds as they are.
+    pub fn expand_one(&mut self) {
+        if self.in_list_view() {
+            return;
+        }
+
+        let Some((id, entries)) = (self.tree_state.selected).zip(self.games.get_entries_mut())
+        else {
+            return;
+        };
+
+        if entries[id].expanded.is_none() {
+            return;
+        }
+
+        let children = entries.children(id).collect::<Vec<_>>();
+
+        for id in std::iter::once(id).chain(children) {
+            if let Some(expanded) = entries[id].expanded.as_mut() {
+                *expanded = true;
+            }
+        }
+    }
+
+    pub fn open_all_folds(&mut self) {
+        if let Some(entries) = self.games.get_entries_mut() {
             entries.apply_to_nodes(|node| {
                 if let Some(expanded) = node.expanded.as_mut() {
                     *expanded = true;
                 }
             });
+
+            if let Some(root) = entries.root_mut() {
+                root.expanded = Some(true);
+            }
         }
     }
 
     pub fn close_all_folds(&mut self) {
+        let in_list_view = self.in_list_view();
+
         if let Some(entries) = self.games.get_entries_mut() {
             entries.apply_to_nodes(|node| {
                 if let Some(expanded) = node.expanded.as_mut() {
@@ -562,43 +1391,331 @@ impl App {
                 }
             });
 
-            if let Some(id) = self.tree_state.selected.and_then(|id| {
-                entries
-                    .ancestors(id)
-                    .filter(|id| *id != NodeId::root())
-                    .last()
-            }) {
+            if !in_list_view
+                && let Some(id) = self.tree_state.selected.and_then(|id| {
+                    entries
+                        .ancestors(id)
+                        .filter(|id| *id != NodeId::root())
+                        .last()
+                })
+            {
                 self.tree_state.selected = Some(id);
             }
         }
     }
 
-    pub fn jump_to_parent(&mut self) {
+    /// Closes the folds of the siblings of the selected entry and of its ancestors, so that only
+    /// the selected branch stays open.
+    pub fn focus_folder(&mut self) {
+        if self.in_list_view() {
+            return;
+        }
+
+        let (Some(id), Some(entries)) = (self.tree_state.selected, self.games.get_entries_mut())
+        else {
+            return;
+        };
+
+        let branch = std::iter::once(id)
+            .chain(entries.ancestors(id))
+            .collect::<Vec<_>>();
+
+        for id in &branch {
+            let siblings = (entries.preceding_siblings(*id))
+                .chain(entries.following_siblings(*id))
+                .collect::<Vec<_>>();
+
+            for sibling in siblings {
+                if let Some(expanded) = entries[sibling].expanded.as_mut() {
+                    *expanded = false;
+                }
+            }
+        }
+    }
+
+    pub fn enter_filter(&mut self) {
+        self.take_input(Mode::Filter);
+
+        if let Some(input) = &mut self.footer_input {
+            input.set_text(&self.filter.pattern);
+        }
+    }
+
+    /// Hides the entries that don't match the filter pattern. Selects the first shown entry if
+    /// the selected one gets hidden.
+    pub fn apply_filter(&mut self) {
+        match self.games.get_entries() {
+            Some(entries) => self.filter.update(entries),
+            None => self.filter.clear(),
+        }
+
+        if self.filter.is_active() {
+            let ids = self.visible_ids();
+
+            if self.tree_state.selected.is_none_or(|id| !ids.contains(&id)) {
+                self.tree_state.selected = ids.first().copied();
+            }
+        } else if let Some(entries) = self.games.get_entries_mut() {
+            self.tree_state.select(self.tree_state.selected, entries);
+        }
+    }
+
+    /// Prompts for the file to write the matches of the search, or the filter if there is no
+    /// search pattern, to.
+    pub fn start_match_export(&mut self) {
+        if self.search.pattern.is_empty() && !self.filter.is_active() {
+            self.message.set_warning("No search or filter is active");
+            return;
+        }
+
+        self.take_input(Mode::MatchExport);
+    }
+
+    /// Writes the paths of the matching entries relative to the profile, one per line, to the
+    /// file in the input. The file can be given to the `--stdin` flag of the subcommands.
+    pub fn export_matches(&mut self) -> Result<()> {
+        let file = PathBuf::from(self.extract_input());
+        let ids = if self.search.pattern.is_empty() {
+            let entries = self.games.get_entries().unwrap();
+            self.filter.find_matches(entries)
+        } else {
+            self.search_match_ids()
+        };
+
+        let profile = self.games.get_profile().unwrap();
+        let entries = &profile.entries;
+
+        let mut content = String::new();
+        for id in &ids {
+            let path = utils::get_relative_path(&profile.path, &entries[*id].path)?;
+            content.push_str(&path.to_string_lossy());
+            content.push('\n');
+        }
+
+        utils::write_atomic(&file, content.as_bytes())?;
+        self.message.set_info(&format!(
+            "Exported {} match(es) to {}",
+            ids.len(),
+            file.display()
+        ));
+
+        Ok(())
+    }
+
+    pub fn clear_filter(&mut self) {
+        if self.filter.is_active() {
+            self.filter.clear();
+            self.apply_filter();
+        }
+    }
+
+    pub fn toggle_flat_view(&mut self) {
+        self.flat_view = !self.flat_view;
+
+        if self.flat_view {
+            if self.selected_entry().is_none_or(|entry| entry.is_folder()) {
+                self.tree_state.selected = self.visible_ids().first().copied();
+            }
+        } else if let Some(entries) = self.games.get_entries_mut() {
+            self.tree_state.select(self.tree_state.selected, entries);
+        }
+    }
+
+    /// Returns true if the entries are navigated as a list instead of a tree, which is the case
+    /// in flat view and while filtering.
+    fn in_list_view(&self) -> bool {
+        self.flat_view || self.filter.is_active()
+    }
+
+    /// Looks up the saves with the same contents as the active save on a background task if the
+    /// active save or the entries changed since the last lookup. Only the saves of the same size
+    /// as the active save, according to the cached metadata of the entries, are hashed.
+    fn update_identical(&mut self) {
+        let identical_of = (self.games.get_profile())
+            .zip(self.tree_state.active)
+            .filter(|_| !OPTIONS.get().identical_marker.is_empty())
+            .map(|(profile, active)| (profile.path.clone(), active));
+
+        if !self.identical_stale && identical_of == self.identical_of {
+            return;
+        }
+
+        self.identical_stale = false;
+        self.identical_of = identical_of;
+        self.identical.clear();
+        self.identical_lookup += 1;
+
+        let (Some(game), Some((_, active))) = (self.games.get_game(), &self.identical_of) else {
+            return;
+        };
+        let entries = &game.get_profile().unwrap().entries;
+
+        let lens = |files: &[(PathBuf, Metadata)]| -> Vec<u64> {
+            files.iter().map(|(_, metadata)| metadata.len).collect()
+        };
+
+        let Some(active_files) = save_files_with_metadata(game, entries, *active) else {
+            return;
+        };
+        let active_lens = lens(&active_files);
+
+        let candidates: Vec<_> = entries
+            .iter_ids()
+            .filter(|id| id != active)
+            .filter_map(|id| Some((id, save_files_with_metadata(game, entries, id)?)))
+            .filter(|(_, files)| lens(files) == active_lens)
+            .collect();
+
+        if candidates.is_empty() {
+            return;
+        }
+
+        let paths: HashSet<PathBuf> = (entries.iter_nodes())
+            .map(|node| node.value.path.clone())
+            .collect();
+        let hash_cache = Arc::clone(&self.hash_cache);
+        let lookup = self.identical_lookup;
+        let tx = self.tx.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let mut hash_cache = hash_cache.lock().unwrap();
+            hash_cache.retain(|path| paths.contains(path));
+
+            let ids = match hash_cache.get_save(&active_files) {
+                Some(active_hash) => (candidates.into_iter())
+                    .filter(|(_, files)| hash_cache.get_save(files) == Some(active_hash))
+                    .map(|(id, _)| id)
+                    .collect(),
+                None => HashSet::new(),
+            };
+
+            let _ = tx.send(Event::IdenticalSaves { lookup, ids });
+        });
+    }
+
+    /// Returns the ids of the entries in the order they are displayed. Only the save files are
+    /// returned in flat view, and only the entries shown by the filter while filtering.
+    pub fn visible_ids(&self) -> Vec<NodeId> {
+        let Some(entries) = self.games.get_entries() else {
+            return Vec::new();
+        };
+
+        let shown = |id: &NodeId| {
+            self.filter
+                .shown
+                .as_ref()
+                .is_none_or(|ids| ids.contains(id))
+        };
+
+        if self.flat_view {
+            entries
+                .iter_ids()
+                .filter(|id| entries[*id].is_file() && shown(id))
+                .collect()
+        } else if self.filter.is_active() {
+            entries.descendants(NodeId::root()).filter(shown).collect()
+        } else {
+            entries.visible(NodeId::root()).collect()
+        }
+    }
+
+    fn select_in_flat_view(&mut self, select: impl FnOnce(Option<usize>, usize) -> usize) {
+        let ids = self.visible_ids();
+
+        if ids.is_empty() {
+            return;
+        }
+
+        let idx = (self.tree_state.selected)
+            .and_then(|selected| ids.iter().position(|id| *id == selected));
+
         self.tree_state
-            .select_unchecked(self.selected_entry().and_then(Node::non_root_parent));
+            .select_unchecked(Some(ids[select(idx, ids.len())]));
+        self.auto_mark_save_file();
+    }
+
+    pub fn jump_to_parent(&mut self) {
+        if self.in_list_view() {
+            return;
+        }
+
+        let parent = (self.tree_state.selected)
+            .zip(self.games.get_entries())
+            .and_then(|(id, entries)| self.tree_state.parent_of(entries, id));
+
+        self.tree_state.select_unchecked(parent);
     }
 
     pub fn on_left(&mut self) {
+        if self.in_list_view() {
+            return;
+        }
+
         let Some(entries) = self.games.get_entries_mut() else {
             return;
         };
 
         if let Some(id) = self.tree_state.selected.and_then(|id| {
-            let node = &entries[id];
-            node.is_expanded().then_some(id).or(node.non_root_parent())
+            entries[id]
+                .is_expanded()
+                .then_some(id)
+                .or(self.tree_state.parent_of(entries, id))
         }) {
             self.tree_state.select_unchecked(Some(id));
             entries[id].toggle_fold();
         }
     }
 
+    /// Loads the selected save or acts on the selected folder as the `enter_action` option
+    /// says.
+    pub fn on_enter(&mut self) {
+        let Some(entry) = self.selected_entry() else {
+            return;
+        };
+
+        let is_save = self.is_save_entry(entry);
+        let is_collapsed = entry.is_collapsed();
+
+        match OPTIONS.get().enter_action {
+            options::EnterAction::Load | options::EnterAction::EnterFolder if is_save => {
+                if self.read_only {
+                    self.warn_read_only();
+                } else {
+                    self.load_selected_save_file();
+                }
+            }
+            options::EnterAction::EnterFolder => {
+                self.on_right();
+
+                let entries = self.games.get_entries().unwrap();
+                if let Some(child) = (self.tree_state.selected)
+                    .and_then(|id| entries.children(id).next())
+                    .filter(|_| !self.in_list_view())
+                {
+                    self.tree_state.select_unchecked(Some(child));
+                    self.auto_mark_save_file();
+                }
+            }
+            _ if is_collapsed => self.on_right(),
+            // closes the selected folder or the folder containing the selected save
+            _ => self.on_left(),
+        }
+    }
+
     pub fn on_up(&mut self) {
-        if let Some(entries) = self.games.get_entries() {
+        if self.in_list_view() {
+            self.select_in_flat_view(|idx, len| idx.map_or(len - 1, |idx| (idx + len - 1) % len));
+        } else if let Some(entries) = self.games.get_entries() {
             self.tree_state.select_prev(entries);
             self.auto_mark_save_file();
         }
     }
+
     pub fn on_right(&mut self) {
+        if self.in_list_view() {
+            return;
+        }
+
         if let Some(entry) = self.selected_entry_mut()
             && entry.is_collapsed()
         {
@@ -607,27 +1724,71 @@ impl App {
     }
 
     pub fn on_down(&mut self) {
-        if let Some(entries) = self.games.get_entries() {
+        if self.in_list_view() {
+            self.select_in_flat_view(|idx, len| idx.map_or(0, |idx| (idx + 1) % len));
+        } else if let Some(entries) = self.games.get_entries() {
             self.tree_state.select_next(entries);
             self.auto_mark_save_file();
         }
     }
 
     pub fn select_first(&mut self) {
-        if let Some(entries) = self.games.get_entries() {
+        if self.in_list_view() {
+            self.select_in_flat_view(|_, _| 0);
+        } else if let Some(entries) = self.games.get_entries() {
             self.tree_state.select_first(entries);
             self.auto_mark_save_file();
         }
     }
 
     pub fn select_last(&mut self) {
-        if let Some(entries) = self.games.get_entries() {
+        if self.in_list_view() {
+            self.select_in_flat_view(|_, len| len - 1);
+        } else if let Some(entries) = self.games.get_entries() {
             self.tree_state.select_last(entries);
             self.auto_mark_save_file();
         }
     }
 
+    /// Selects the entry at `line` of the tree, counting from 1. `line` is clamped to the number
+    /// of the displayed entries.
+    pub fn go_to_line(&mut self, line: usize) {
+        let ids = self.displayed_ids();
+
+        if ids.is_empty() {
+            return;
+        }
+
+        let idx = line.clamp(1, ids.len()) - 1;
+        self.tree_state.select_unchecked(Some(ids[idx]));
+        self.auto_mark_save_file();
+    }
+
+    /// Returns the ids of the entries on the lines of the tree, in order. Unlike
+    /// [`App::visible_ids`], the compacted folders are left out and the root is included if it is
+    /// visible.
+    fn displayed_ids(&self) -> Vec<NodeId> {
+        let Some(entries) = self.games.get_entries() else {
+            return Vec::new();
+        };
+
+        let root = (self.tree_state.root_visible && !self.flat_view).then_some(NodeId::root());
+        let ids = if self.in_list_view() {
+            self.visible_ids()
+        } else {
+            (entries.visible(NodeId::root()))
+                .filter(|id| !self.tree_state.is_compacted(entries, *id))
+                .collect()
+        };
+
+        root.into_iter().chain(ids).collect()
+    }
+
     pub fn up_directory(&mut self) {
+        if self.in_list_view() {
+            return;
+        }
+
         if let Some(id) = self.tree_state.selected {
             if let Some(entries) = self.games.get_entries() {
                 self.tree_state.select_unchecked(
@@ -636,6 +1797,10 @@ impl App {
                         .chain(entries.children(NodeId::root()).rev())
                         .find(|id| entries[*id].is_folder() && *id != NodeId::root()),
                 );
+
+                if OPTIONS.get().expand_on_directory_jump {
+                    self.on_right();
+                }
             }
         } else {
             self.select_first();
@@ -643,6 +1808,10 @@ impl App {
     }
 
     pub fn down_directory(&mut self) {
+        if self.in_list_view() {
+            return;
+        }
+
         if let Some(id) = self.tree_state.selected {
             if let Some(entries) = self.games.get_entries() {
                 self.tree_state.select_unchecked(
@@ -656,6 +1825,10 @@ impl App {
                         .chain(entries.children(NodeId::root()))
                         .find(|id| entries[*id].is_folder() && *id != NodeId::root()),
                 );
+
+                if OPTIONS.get().expand_on_directory_jump {
+                    self.on_right();
+                }
             }
         } else {
             self.select_last();
@@ -664,18 +1837,33 @@ impl App {
 
     pub fn load_save_file(&mut self, path: &Path, mark_as_active: bool) -> Result<()> {
         let game = self.games.get_game_unchecked_mut();
-        let Some(savefile_path) = &game.savefile_path else {
+        if game.savefile_paths.is_empty() {
             self.message
                 .set_warning("No savefile path is set for the game.");
             return Ok(());
-        };
+        }
+
+        // the live save is left as it is if the save can't be loaded, so it isn't backed up either
+        let backup =
+            if (self.backup_on_load || OPTIONS.get().backup_before_load) && game.is_save(path) {
+                Self::backup_live_save(game).context("couldn't back up the live save")?
+            } else {
+                None
+            };
 
-        std::fs::copy(path, savefile_path).context("couldn't load save file")?;
+        (game.copy_savefiles_from(path, "load")).context("couldn't load save file")?;
 
         let profile = game.get_profile_mut().unwrap();
 
-        self.message
-            .set_message_with_timeout(&format!("Loaded {}", profile.rel_path_to(path)), 5);
+        let path_to_show = profile.display_path(path, self.absolute_paths);
+        let msg = match backup {
+            Some(backup) => format!(
+                "Loaded {path_to_show}, backed up the live save to {}",
+                profile.display_path(&backup, self.absolute_paths)
+            ),
+            None => format!("Loaded {path_to_show}"),
+        };
+        self.message.set_message_with_timeout(&msg, 5);
 
         if mark_as_active {
             profile.update_active_save_file(path)?;
@@ -685,9 +1873,41 @@ impl App {
         Ok(())
     }
 
+    /// Copies the live save into the `backup` folder of the selected profile and returns the path
+    /// of the copy, or `None` if there is no live save to back up.
+    fn backup_live_save(game: &Game) -> Result<Option<PathBuf>> {
+        if !game.savefile_paths.iter().all(|path| path.exists()) {
+            return Ok(None);
+        }
+
+        let folder = game.get_profile().unwrap().abs_path_to("backup");
+        if !folder.exists() {
+            let res = std::fs::create_dir_all(&folder);
+            utils::log("create folder", &[&folder], &res);
+            res?;
+        }
+
+        let savefile_path = game.import_name_source().unwrap();
+        let mut path =
+            utils::expand_import_name("{datetime}", &folder, &game.name(), &savefile_path);
+        utils::validate_name(&mut path);
+        game.copy_savefiles_to(&path, "backup")?;
+
+        Ok(Some(path))
+    }
+
+    /// Returns true if the entry is a save of the selected game. Files are always treated as saves
+    /// unless the game has multiple save files, in which case saves are folders holding them.
+    fn is_save_entry(&self, entry: &Entry) -> bool {
+        match self.games.get_game() {
+            Some(game) if game.savefile_paths.len() > 1 => game.is_save(&entry.path),
+            _ => entry.is_file(),
+        }
+    }
+
     pub fn load_selected_save_file(&mut self) {
         if let Some(entry) = self.selected_entry()
-            && entry.is_file()
+            && self.is_save_entry(entry)
         {
             let path = entry.path.clone();
             set_msg_if_error!(self.message, self.load_save_file(&path, true));
@@ -695,15 +1915,17 @@ impl App {
     }
 
     pub fn load_random_save_file(&mut self) {
-        let Some(entries) = self.games.get_entries_mut() else {
+        let Some(entries) = self.games.get_entries() else {
             return;
         };
 
         let save_files = entries
             .iter_ids()
-            .filter(|id| !entries.detached_from_root(*id) && entries[*id].is_file())
+            .filter(|id| !entries.detached_from_root(*id) && self.is_save_entry(&entries[*id]))
             .collect::<Vec<NodeId>>();
 
+        let entries = self.games.get_entries_mut().unwrap();
+
         let id = fastrand::choice(save_files);
         self.tree_state.select(id, entries);
 
@@ -726,7 +1948,7 @@ impl App {
     pub fn mark_selected_save_file(&mut self) {
         if let Some(path) = self
             .selected_entry()
-            .filter(|entry| entry.is_file())
+            .filter(|entry| self.is_save_entry(entry))
             .map(|entry| entry.path.clone())
         {
             let profile = self.games.get_profile_mut().unwrap();
@@ -735,46 +1957,169 @@ impl App {
         }
     }
 
+    pub fn unmark_active_save_file(&mut self) {
+        let Some(profile) = self.games.get_profile_mut() else {
+            return;
+        };
+
+        if profile.get_active_save_file().is_none() {
+            self.message
+                .set_warning("No save file is marked as active.");
+            return;
+        }
+
+        set_msg_if_error!(self.message, profile.reset_active_save_file());
+        self.tree_state.active = None;
+    }
+
     pub fn auto_mark_save_file(&mut self) {
-        if OPTIONS.auto_mark_save_file {
+        if OPTIONS.get().auto_mark_save_file && !self.read_only {
             self.mark_selected_save_file();
         }
     }
 
     pub fn import_save_file(&mut self, top_level: bool) {
-        let Some(savefile_path) = self.games.get_game_unchecked().savefile_path.clone() else {
+        let game = self.games.get_game_unchecked();
+        let Some(savefile_path) = game.import_name_source() else {
             self.message
                 .set_warning("No savefile path is set for the game.");
             return;
         };
 
-        let node = self.context_node(top_level);
-        let mut path = node.path.join(savefile_path.file_name().unwrap());
-        utils::validate_name(&mut path);
+        let game_name = game.name().into_owned();
+        let mut dir = self.context_node(top_level).path.clone();
+        if OPTIONS.get().import_group_by_date {
+            dir.push(utils::current_date());
+        }
 
-        if let Err(e) = std::fs::copy(&savefile_path, &path) {
-            self.message.set_error(&e.into());
-        } else {
-            node.expanded = Some(true);
+        let mut path = utils::expand_import_name(
+            &OPTIONS.get().import_name_template,
+            &dir,
+            &game_name,
+            &savefile_path,
+        );
+
+        if path.exists() {
+            if OPTIONS.get().import_overwrite && self.games.get_game_unchecked().is_save(&path) {
+                self.pending_import = Some(path);
+                self.prompt_for_confirmation(ConfirmationContext::ImportOverwrite);
+                return;
+            }
+
+            utils::validate_name(&mut path);
         }
+
+        set_msg_if_error!(self.message, self.import_save_file_to(&path));
+    }
+
+    fn import_save_file_to(&mut self, path: &Path) -> Result<()> {
+        // the folder of the date doesn't exist for the first import of the day
+        if let Some(parent) = path.parent()
+            && !parent.exists()
+        {
+            let res = std::fs::create_dir_all(parent);
+            utils::log("create folder", &[parent], &res);
+            res?;
+        }
+
+        (self.games.get_game_unchecked()).copy_savefiles_to(path, "import")?;
+        self.message.ring_bell();
+
+        let entries = self.games.get_entries_mut().unwrap();
+        if let Some(parent_id) = path
+            .ancestors()
+            .skip(1)
+            .find_map(|ancestor| entries.find_by_path(ancestor))
+        {
+            entries[parent_id].expanded = Some(true);
+        }
+
+        if OPTIONS.get().mark_import_active {
+            // an overwritten save is already in the tree, a new one is added by the watcher
+            match entries.find_by_path(path) {
+                Some(id) => self.activate_imported(id)?,
+                None => self.pending_activation = Some(path.to_path_buf()),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Selects the imported save and marks it as active.
+    fn activate_imported(&mut self, id: NodeId) -> Result<()> {
+        let profile = self.games.get_profile_mut().unwrap();
+        self.tree_state.select(Some(id), &mut profile.entries);
+        self.tree_state.active = Some(id);
+
+        let path = profile.entries[id].path.clone();
+        profile.update_active_save_file(&path)
+    }
+
+    fn confirm_import_overwrite(&mut self) -> Result<()> {
+        let Some(path) = self.pending_import.take() else {
+            return Ok(());
+        };
+
+        self.import_save_file_to(&path)
     }
 
     pub fn replace_save_file(&mut self) -> Result<()> {
+        let game = self.games.get_game_unchecked();
+
+        if game.savefile_paths.is_empty() {
+            self.message
+                .set_warning("No savefile path is set for the game.");
+        } else if let Some(entry) = self.selected_entry()
+            && game.is_save(&entry.path)
+        {
+            game.copy_savefiles_to(&entry.path, "replace")?;
+        }
+
+        Ok(())
+    }
+
+    /// Copies the files of the active save over the files of the selected save.
+    pub fn replace_with_active_save_file(&mut self) -> Result<()> {
+        let Some(active_path) = self
+            .games
+            .get_profile()
+            .and_then(Profile::get_active_save_file)
+        else {
+            return Ok(());
+        };
+
         if let Some(entry) = self.selected_entry()
-            && entry.is_file()
+            && self.is_save_entry(entry)
         {
-            if let Some(savefile_path) = &self.games.get_game_unchecked().savefile_path {
-                std::fs::copy(savefile_path, &entry.path)?;
-            } else {
-                self.message
-                    .set_warning("No savefile path is set for the game.");
+            let game = self.games.get_game_unchecked();
+
+            for (from, to) in
+                (game.save_files_in(&active_path).iter()).zip(game.save_files_in(&entry.path))
+            {
+                let res = std::fs::copy(from, &to);
+                utils::log("replace", &[from, &to], &res);
+                res?;
             }
         }
 
         Ok(())
     }
 
+    /// Opens the fuzzy finder over the entries of the selected profile, or of all the profiles if
+    /// `global` is set. In the game and profile selection windows, it is opened over the listed
+    /// games or profiles instead.
     pub fn open_fuzzy_finder(&mut self, global: bool) {
+        match self.mode {
+            Mode::GameSelection => self.fuzzy_finder.set_picker(GameList::new(self)),
+            Mode::ProfileSelection => self.fuzzy_finder.set_picker(ProfileList::new(self)),
+            _ => (),
+        }
+
+        if self.fuzzy_finder.is_active() {
+            self.fuzzy_finder.update_matches();
+            return;
+        }
+
         if global {
             match Global::new(self) {
                 Ok(picker) => self.fuzzy_finder.set_picker(picker),
@@ -784,6 +2129,10 @@ impl App {
             self.fuzzy_finder.set_picker(Local::new(self));
         }
 
+        if OPTIONS.get().fuzzy_remember_query {
+            self.fuzzy_finder.restore_last_query();
+        }
+
         self.fuzzy_finder.update_matches();
     }
 
@@ -795,8 +2144,394 @@ impl App {
         }
     }
 
+    /// Starts sending the autosave events every `autosave_interval` seconds, stopping the events
+    /// scheduled before.
+    fn schedule_autosave(&mut self) {
+        if let Some(task) = self.autosave_task.take() {
+            task.abort();
+        }
+
+        if OPTIONS.get().autosave_interval == 0 {
+            return;
+        }
+
+        let tx = self.tx.clone();
+        let period = Duration::from_secs(OPTIONS.get().autosave_interval);
+
+        self.autosave_task = Some(tokio::spawn(async move {
+            let mut interval = tokio::time::interval_at(Instant::now() + period, period);
+
+            loop {
+                interval.tick().await;
+
+                if tx.send(Event::Autosave).is_err() {
+                    break;
+                }
+            }
+        }));
+    }
+
+    /// Copies the live save of the game into the least recently written autosave slot of the
+    /// profile, unless it hasn't changed since the last autosave.
+    fn autosave(&mut self) -> Result<()> {
+        let Some(game) = self.games.get_game().filter(|_| !self.read_only) else {
+            return Ok(());
+        };
+
+        let Some(profile) = game
+            .get_profile()
+            .filter(|_| !game.savefile_paths.is_empty())
+        else {
+            return Ok(());
+        };
+
+        let modified = game
+            .savefile_paths
+            .iter()
+            .map(|path| std::fs::metadata(path).and_then(|metadata| metadata.modified()))
+            .collect::<std::io::Result<Vec<_>>>()?
+            .into_iter()
+            .max();
+        if modified.is_none() || self.last_autosave == modified {
+            return Ok(());
+        }
+
+        let folder = profile.abs_path_to("autosave");
+        std::fs::create_dir_all(&folder)?;
+
+        let ext = match game.savefile_paths.as_slice() {
+            [savefile_path] => savefile_path
+                .extension()
+                .map_or(String::new(), |ext| format!(".{}", ext.to_string_lossy())),
+            _ => String::new(),
+        };
+
+        let slot = (1..=AUTOSAVE_SLOTS)
+            .map(|n| folder.join(format!("autosave-{n}{ext}")))
+            .min_by_key(|path| {
+                game.save_files_in(path)
+                    .iter()
+                    .filter_map(|file| std::fs::metadata(file).and_then(|m| m.modified()).ok())
+                    .max()
+            })
+            .unwrap();
+
+        game.copy_savefiles_to(&slot, "autosave")?;
+
+        self.last_autosave = modified;
+        let slot = profile.display_path(&slot, self.absolute_paths);
+        self.message
+            .set_message_with_timeout(&format!("Autosaved to {slot}"), 5);
+        self.message.ring_bell();
+
+        Ok(())
+    }
+
+    pub fn toggle_absolute_paths(&mut self) {
+        self.absolute_paths = !self.absolute_paths;
+        self.message.set_info(if self.absolute_paths {
+            "Showing absolute paths"
+        } else {
+            "Showing paths relative to the profile"
+        });
+    }
+
+    pub fn toggle_read_only(&mut self) {
+        self.read_only = !self.read_only;
+        self.message.set_info(if self.read_only {
+            "Read-only mode enabled"
+        } else {
+            "Read-only mode disabled"
+        });
+    }
+
+    pub fn warn_read_only(&mut self) {
+        self.message
+            .set_warning("Read-only mode is enabled. Toggle it to modify the save files.");
+    }
+
+    /// Returns the paths of the two marked files, failing if a different number of files is
+    /// marked.
+    fn marked_file_pair(&self, operation: &str) -> Result<[PathBuf; 2]> {
+        let entries = self.games.get_entries().unwrap();
+        let paths = entries
+            .iter_ids()
+            .filter(|id| self.tree_state.marked.contains(id) && entries[*id].is_file())
+            .map(|id| entries[id].path.clone())
+            .collect::<Vec<PathBuf>>();
+
+        paths
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("Exactly two files must be marked to {operation}."))
+    }
+
+    pub fn diff_marked(&mut self) -> Result<()> {
+        let paths = self.marked_file_pair("diff")?;
+
+        let options = OPTIONS.get();
+        let mut args = options.diff_command.split_whitespace().map(str::to_owned);
+        let program = args.next().context("Diff command can't be empty.")?;
+
+        let mut command = std::process::Command::new(program);
+        command.args(args).args(paths);
+
+        self.external_command = Some(ExternalCommand {
+            command,
+            wait_for_enter: true,
+            after: None,
+        });
+
+        Ok(())
+    }
+
+    /// Swaps the contents of the two marked files through a temporary file, keeping their names.
+    pub fn swap_contents(&mut self) -> Result<()> {
+        let [a, b] = self.marked_file_pair("swap")?;
+        // hidden so that the watcher ignores it
+        let tmp = a.with_file_name(".bawa-swap");
+
+        let res = std::fs::copy(&a, &tmp)
+            .and_then(|_| std::fs::copy(&b, &a))
+            .and_then(|_| std::fs::copy(&tmp, &b))
+            .and_then(|_| std::fs::remove_file(&tmp));
+        utils::log("swap", &[&a, &b], &res);
+        res?;
+
+        let profile = self.games.get_profile().unwrap();
+        self.message.set_message_with_timeout(
+            &format!(
+                "Swapped the contents of {} and {}",
+                profile.display_path(&a, self.absolute_paths),
+                profile.display_path(&b, self.absolute_paths)
+            ),
+            5,
+        );
+
+        Ok(())
+    }
+
+    /// Copies the selected entry to the top level of the other profiles of the game, and of the
+    /// current one too if the `broadcast_to_current` option is set. The profiles don't need to be
+    /// loaded as the copies are made on the file system, on a background task for large folders.
+    pub fn broadcast_to_profiles(&mut self) {
+        let (Some(id), Some(game)) = (self.selected_id(), self.games.get_game()) else {
+            return;
+        };
+
+        let from = game.get_profile().unwrap().entries[id].path.clone();
+        let background = utils::count_files(&from) > BACKGROUND_COPY_THRESHOLD;
+        let mut background_copies = Vec::new();
+        let mut copied = 0;
+        let mut failed = Vec::new();
+
+        for (idx, profile) in game.profiles.items.iter().enumerate() {
+            if game.active_profile == Some(idx) && !OPTIONS.get().broadcast_to_current {
+                continue;
+            }
+
+            let mut to = profile.path.join(from.file_name().unwrap());
+            utils::validate_name(&mut to);
+
+            if background {
+                background_copies.push((from.clone(), to));
+                continue;
+            }
+
+            let res = utils::copy_recursive(&from, &to, &mut || ());
+            utils::log("broadcast", &[&from, &to], &res);
+
+            match res {
+                Ok(()) => copied += 1,
+                Err(_) => failed.push(profile.name().into_owned()),
+            }
+        }
+
+        if background {
+            self.copy_in_background(background_copies, false);
+        } else if failed.is_empty() {
+            self.message
+                .set_message_with_timeout(&format!("Copied to {copied} profile(s)"), 5);
+        } else {
+            self.message.set_warning(&format!(
+                "Copied to {copied} profile(s), failed for {}",
+                failed.join(", ")
+            ));
+        }
+    }
+
+    /// Returns the total size of the files among the given entries and inside them. The files
+    /// inside more than one of them are counted once.
+    pub fn total_size(&self, ids: &[NodeId]) -> u64 {
+        let Some(entries) = self.games.get_entries() else {
+            return 0;
+        };
+
+        (ids.iter().flat_map(|id| entries.descendants(*id)))
+            .filter(|id| entries[*id].is_file())
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .map(|id| entries[id].metadata().map_or(0, |metadata| metadata.len))
+            .sum()
+    }
+
+    /// Shows the total size and the number of the files and folders in the profile, along with
+    /// the largest file.
+    pub fn show_disk_usage(&mut self) {
+        let Some(entries) = self.games.get_entries() else {
+            return;
+        };
+
+        let (mut size, mut files, mut folders) = (0, 0, 0);
+        let mut largest: Option<(u64, NodeId)> = None;
+
+        for id in entries.descendants(NodeId::root()).skip(1) {
+            let entry = &entries[id].value;
+
+            if entry.is_folder() {
+                folders += 1;
+                continue;
+            }
+
+            let len = entry.metadata().map_or(0, |metadata| metadata.len);
+            size += len;
+            files += 1;
+
+            if largest.is_none_or(|(largest_len, _)| len > largest_len) {
+                largest = Some((len, id));
+            }
+        }
+
+        let mut message = format!(
+            "{} in {files} file(s) and {folders} folder(s)",
+            utils::format_size(size)
+        );
+
+        if let Some((len, id)) = largest {
+            let profile = self.games.get_profile().unwrap();
+            let path = profile.display_path(&entries[id].path, self.absolute_paths);
+            message.push_str(&format!(", largest: {path} ({})", utils::format_size(len)));
+        }
+
+        self.message.set_info(&message);
+    }
+
+    /// Opens `$SHELL` in the folder of the selected profile, or of the selected game if no profile
+    /// is selected. The app is resumed once the shell exits.
+    pub fn open_shell(&mut self) {
+        let shell = std::env::var_os(if cfg!(windows) { "COMSPEC" } else { "SHELL" })
+            .unwrap_or_else(|| (if cfg!(windows) { "cmd" } else { "sh" }).into());
+
+        let mut command = std::process::Command::new(shell);
+
+        if let Some(profile) = self.games.get_profile() {
+            command.current_dir(&profile.path);
+        } else if let Some(game) = self.games.get_game() {
+            command.current_dir(&game.path);
+        }
+
+        self.external_command = Some(ExternalCommand {
+            command,
+            wait_for_enter: false,
+            after: None,
+        });
+    }
+
+    /// Opens the config file in `$VISUAL` or `$EDITOR` and reloads it once the editor exits.
+    pub fn edit_config(&mut self) -> Result<()> {
+        let path = config::config_path()?
+            .context("The config file is ignored because of the --no-config flag.")?;
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let editor = std::env::var("VISUAL")
+            .or_else(|_| std::env::var("EDITOR"))
+            .unwrap_or_else(|_| String::from(if cfg!(windows) { "notepad" } else { "vi" }));
+        let mut args = editor.split_whitespace().map(str::to_owned);
+        let program = args.next().context("Editor command can't be empty.")?;
+
+        let mut command = std::process::Command::new(program);
+        command.args(args).arg(path);
+
+        self.external_command = Some(ExternalCommand {
+            command,
+            wait_for_enter: false,
+            after: Some(App::reload_config),
+        });
+
+        Ok(())
+    }
+
+    /// Reads the config file again and applies it. Options that only take effect on start up,
+    /// such as `alternate_screen`, still need a restart.
+    pub fn reload_config(&mut self) {
+        let autosave_interval = OPTIONS.get().autosave_interval;
+
+        if let Err(e) = config::reload() {
+            self.message.set_error(&e);
+            return;
+        }
+
+        if OPTIONS.get().autosave_interval != autosave_interval {
+            self.schedule_autosave();
+        }
+
+        self.help.bindings = Bindings::default();
+        self.tree_state.root_visible = OPTIONS.get().show_root;
+        self.tree_state.compact_folders = OPTIONS.get().compact_folders;
+
+        if !OPTIONS.get().show_root && self.tree_state.selected == Some(NodeId::root()) {
+            self.select_first();
+        }
+
+        self.message.set_info("Reloaded the config file.");
+    }
+
+    pub fn toggle_pin(&mut self) -> Result<()> {
+        let Some(id) = self.selected_id() else {
+            return Ok(());
+        };
+
+        let profile = self.games.get_profile_mut().unwrap();
+        let path = profile.entries[id].path.clone();
+        let pinned = profile.toggle_pin(&path)?;
+
+        if pinned
+            && OPTIONS.get().pinned_first
+            && let Some(parent) = profile.entries[id].parent()
+        {
+            profile.entries.move_entry(Tree::prepend, parent, id);
+            profile.write_state()?;
+        }
+
+        Ok(())
+    }
+
+    pub fn open_content_search(&mut self) {
+        let picker = Content::new(self);
+
+        if picker.is_empty() {
+            self.message.set_warning("No text save files found.");
+        } else {
+            self.fuzzy_finder.set_picker(picker);
+            self.fuzzy_finder.update_matches();
+        }
+    }
+
+    pub fn open_pinned_picker(&mut self) {
+        let picker = Local::pinned(self);
+
+        if picker.is_empty() {
+            self.message.set_warning("No entries are pinned.");
+        } else {
+            self.fuzzy_finder.set_picker(picker);
+            self.fuzzy_finder.update_matches();
+        }
+    }
+
     pub fn mark_entry(&mut self) {
-        if let Some(id) = self.tree_state.selected {
+        if let Some(id) = self.selected_id() {
             if !self.tree_state.unmark(id) {
                 self.tree_state.mark(id);
             }
@@ -806,6 +2541,76 @@ impl App {
     }
 }
 
+/// Returns the files of the save `id` with their cached metadata, or `None` if `id` isn't a save
+/// or the metadata of any of its files can't be read.
+fn save_files_with_metadata(
+    game: &Game,
+    entries: &Tree<Entry>,
+    id: NodeId,
+) -> Option<Vec<(PathBuf, Metadata)>> {
+    let entry = &entries[id];
+
+    match game.savefile_paths.len() {
+        0 => None,
+        1 if entry.is_file() => Some(vec![(entry.path.clone(), entry.metadata()?)]),
+        1 => None,
+        _ if entry.is_file() => None,
+        _ => game
+            .save_files_in(&entry.path)
+            .into_iter()
+            .map(|file| {
+                let child = entries
+                    .children(id)
+                    .find(|child| entries[*child].path == file)?;
+                Some((file, entries[child].metadata()?))
+            })
+            .collect(),
+    }
+}
+
+fn cycle_index(idx: usize, len: usize, forward: bool) -> usize {
+    if forward {
+        (idx + 1) % len
+    } else {
+        (idx + len - 1) % len
+    }
+}
+
+pub struct ExternalCommand {
+    pub command: std::process::Command,
+    /// Whether to wait for the user to press enter after the program exits so that its output
+    /// can be read.
+    pub wait_for_enter: bool,
+    /// Called once the program exits successfully.
+    pub after: Option<fn(&mut App)>,
+}
+
+impl ExternalCommand {
+    /// Runs the program and returns whether it exited successfully. A failure isn't an error as
+    /// some programs, such as diff tools, exit with a non-zero status to report their result.
+    fn run(mut self) -> Result<bool> {
+        let program = self.command.get_program().to_string_lossy().into_owned();
+        let status = self
+            .command
+            .status()
+            .with_context(|| format!("Couldn't run {program}"))?;
+
+        if self.wait_for_enter {
+            print!("\nPress enter to return to {}", env!("CARGO_PKG_NAME"));
+            std::io::stdout().flush()?;
+            std::io::stdin().read_line(&mut String::new())?;
+        }
+
+        Ok(status.success())
+    }
+}
+
+pub struct Clipboard {
+    pub paths: Vec<PathBuf>,
+    /// Whether the entries are moved instead of copied by pasting.
+    pub cut: bool,
+}
+
 struct HandleMove {
     count: u32,
     relative: NodeId,
@@ -835,7 +2640,15 @@ impl HandleFileSystemEvent for App {
 
         if let Some(parent_id) = path.parent().and_then(|path| entries.find_by_path(path)) {
             let new = entries.add_value(Entry::new(path));
-            entries.append(parent_id, new);
+
+            if OPTIONS.get().folders_first && entries[new].is_folder() {
+                match (entries.children(parent_id)).rfind(|id| entries[*id].is_folder()) {
+                    Some(last_folder) => entries.insert_after(last_folder, new),
+                    None => entries.prepend(parent_id, new),
+                }
+            } else {
+                entries.append(parent_id, new);
+            }
 
             let node = &mut entries[new];
 
@@ -851,6 +2664,20 @@ impl HandleFileSystemEvent for App {
             {
                 self.tree_state.select_unchecked(Some(new));
             }
+
+            if self.pending_selection.as_deref() == Some(path) {
+                self.pending_selection = None;
+
+                if !self.in_list_view() {
+                    let entries = self.games.get_entries_mut().unwrap();
+                    self.tree_state.select(Some(new), entries);
+                }
+            }
+
+            if self.pending_activation.as_deref() == Some(path) {
+                self.pending_activation = None;
+                self.activate_imported(new)?;
+            }
         }
 
         Ok(())
@@ -881,28 +2708,47 @@ impl HandleFileSystemEvent for App {
         }
 
         profile.entries.update_paths(entry_id, new_path)?;
+        profile.rename_pinned(path, new_path);
 
-        if matches!(profile.get_active_save_file(), Some(active_path) if active_path == path) {
-            profile.update_active_save_file(new_path)?;
-        } else {
+        if !profile.rename_active_save_file(path, new_path)? {
             set_msg_if_error!(self.message, profile.write_state());
         }
 
         Ok(())
     }
 
+    fn on_modify(&mut self, path: &Path) -> Result<()> {
+        if let Some(entries) = self.games.get_entries_mut()
+            && let Some(id) = entries.find_by_path(path)
+        {
+            entries[id].invalidate_metadata();
+        }
+
+        Ok(())
+    }
+
     fn on_delete(&mut self, path: &Path) -> Result<()> {
+        let next_selection = self.selection_after_delete(path);
+
         let Some(profile) = self.games.get_profile_mut().filter(|p| p.path.exists()) else {
             return Ok(());
         };
 
-        if matches!(profile.get_active_save_file(), Some(active_path) if active_path == path) {
+        let unpinned = profile.remove_pinned(path);
+
+        if matches!(profile.get_active_save_file(), Some(active_path) if active_path.starts_with(path))
+        {
             profile.reset_active_save_file()?;
+        } else if unpinned {
+            profile.write_state()?;
         }
 
         if let Some(entry_id) = profile.entries.find_by_path(path) {
             if matches!(self.tree_state.selected, Some(id) if id == entry_id) {
-                self.tree_state.select_prev(&profile.entries);
+                match next_selection {
+                    Some(id) => self.tree_state.selected = Some(id),
+                    None => self.tree_state.select_prev(&profile.entries),
+                }
             }
             self.tree_state.unmark(entry_id);
             profile.entries.detach(entry_id);
@@ -946,13 +2792,17 @@ impl<T> StatefulList<T> {
         });
     }
 
-    pub fn next(&mut self) {
+    /// Selects the next item. The first item follows the last one if `wrap` is set, otherwise
+    /// the selection stays on the last one.
+    pub fn next(&mut self, wrap: bool) {
         let i = match self.state.selected() {
             Some(i) => {
-                if i >= self.items.len() - 1 {
+                if i < self.items.len().saturating_sub(1) {
+                    i + 1
+                } else if wrap {
                     0
                 } else {
-                    i + 1
+                    i
                 }
             }
             None => 0,
@@ -960,13 +2810,17 @@ impl<T> StatefulList<T> {
         self.select_with_index(i);
     }
 
-    pub fn previous(&mut self) {
+    /// Selects the previous item. The last item precedes the first one if `wrap` is set,
+    /// otherwise the selection stays on the first one.
+    pub fn previous(&mut self, wrap: bool) {
         let i = match self.state.selected() {
             Some(i) => {
-                if i == 0 {
-                    self.items.len() - 1
-                } else {
+                if i > 0 {
                     i - 1
+                } else if wrap {
+                    self.items.len().saturating_sub(1)
+                } else {
+                    i
                 }
             }
             None => 0,
@@ -979,7 +2833,7 @@ impl<T> StatefulList<T> {
     }
 
     pub fn select_last(&mut self) {
-        self.select_with_index(self.items.len().checked_sub(1).unwrap_or_default());
+        self.select_with_index(self.items.len().saturating_sub(1));
     }
 
     pub fn get_selected(&self) -> Option<&T> {
@@ -9,13 +9,28 @@ pub fn create_entry_subcommands() -> Vec<Command> {
         .long("fuzzy")
         .action(ArgAction::SetTrue);
 
-    let mut relative_path = Arg::new("relative_path")
+    let relative_path = Arg::new("relative_path")
         .help("relative path to save file from profile")
         .value_name("RELATIVE_PATH")
         .add(ArgValueCompleter::new(completion::entry_completer));
 
+    let stdin = Arg::new("stdin")
+        .help("read newline-separated relative paths from stdin and act on each")
+        .long("stdin")
+        .conflicts_with_all(["relative_path", "fuzzy"])
+        .action(ArgAction::SetTrue);
+
     vec![
-        Command::new("list").about("list save files"),
+        Command::new("list").about("list save files").arg(
+            Arg::new("format")
+                .help(
+                    "print each entry using the template with the placeholders {path}, {name}, \
+                     {is_folder}, {active}, {size}, {mtime} and {created}, where {created} \
+                     falls back to {mtime} if the creation time isn't available",
+                )
+                .long("format")
+                .value_name("TEMPLATE"),
+        ),
         Command::new("load")
             .about("load save file")
             .arg(&relative_path)
@@ -24,26 +39,58 @@ pub fn create_entry_subcommands() -> Vec<Command> {
                 Arg::new("random")
                     .help("load a random save file")
                     .short('r')
-                    .conflicts_with("fuzzy")
+                    .conflicts_with_all(["fuzzy", "stdin"])
                     .long("random")
                     .action(ArgAction::SetTrue),
-            ),
+            )
+            .arg(
+                Arg::new("backup")
+                    .help("copy the live save into the backup folder of the profile before loading")
+                    .short('b')
+                    .long("backup")
+                    .action(ArgAction::SetTrue),
+            )
+            .arg(&stdin),
         Command::new("import").about("import save file"),
         Command::new("rename")
             .about("rename save file")
-            .arg(Arg::new("new_name").required(true).value_name("NEW_NAME"))
-            .arg({
-                relative_path = relative_path.required_unless_present("fuzzy");
-                &relative_path
-            })
+            .arg(
+                Arg::new("new_name")
+                    .required_unless_present("stdin")
+                    .conflicts_with("stdin")
+                    .value_name("NEW_NAME"),
+            )
+            .arg(
+                relative_path
+                    .clone()
+                    .required_unless_present_any(["fuzzy", "stdin"]),
+            )
+            .arg(&fuzzy)
+            .arg(
+                stdin
+                    .clone()
+                    .help("read newline-separated RELATIVE_PATH<TAB>NEW_NAME pairs from stdin"),
+            ),
+        Command::new("mark")
+            .about("mark save file as active without loading it")
+            .arg(relative_path.clone().required_unless_present("fuzzy"))
             .arg(&fuzzy),
         Command::new("delete")
             .about("delete save file")
-            .arg(relative_path)
-            .arg(fuzzy),
+            .arg(relative_path.required_unless_present_any(["fuzzy", "stdin"]))
+            .arg(fuzzy)
+            .arg(stdin),
     ]
 }
 
+pub fn create_doctor_subcommand() -> Command {
+    Command::new("doctor").about("report the environment for troubleshooting")
+}
+
+pub fn create_keys_subcommand() -> Command {
+    Command::new("keys").about("print the key bindings with the configuration file applied")
+}
+
 pub fn create_game_subcommand() -> Command {
     let by_index = Arg::new("by_index")
         .short('i')
@@ -62,6 +109,7 @@ pub fn create_game_subcommand() -> Command {
                 .arg(
                     Arg::new("savefile_path")
                         .required(true)
+                        .num_args(1..)
                         .value_parser(ValueParser::path_buf())
                         .value_name("PATH"),
                 ),
@@ -8,6 +8,7 @@ use clap_complete::CompletionCandidate;
 use std::{ffi::OsStr, path::MAIN_SEPARATOR};
 
 pub fn game_completer(current: &OsStr) -> Vec<CompletionCandidate> {
+    SKIP_CONFIG.call_once(|| {});
     let Some(current) = current.to_str() else {
         return Vec::new();
     };
@@ -24,6 +25,7 @@ pub fn game_completer(current: &OsStr) -> Vec<CompletionCandidate> {
 }
 
 pub fn profile_completer(current: &OsStr) -> Vec<CompletionCandidate> {
+    SKIP_CONFIG.call_once(|| {});
     let Some(current) = current.to_str() else {
         return Vec::new();
     };
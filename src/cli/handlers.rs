@@ -1,22 +1,56 @@
-use super::CLAP_ARGS;
+use super::{CLAP_ARGS, template::Template};
 use crate::{
     app::App,
+    config::{self, Config, KEY_BINDINGS, SKIP_CONFIG},
     fuzzy_finder::picker::Local,
-    game::{Game, Games},
-    tree::{TreeState, widget::Tree},
+    game::{Game, Games, creation, get_active_game, read_games},
+    help::{Bindings, Section},
+    tree::{NodeId, TreeState, widget::Tree},
     utils,
+    watcher::HandleFileSystemEvent,
 };
-use anyhow::{Context, Result};
-use clap::{ArgMatches, parser::ValueSource};
-use crossterm::style::Stylize;
-use std::path::PathBuf;
+use anyhow::{Context, Result, ensure};
+use clap::ArgMatches;
+use crossterm::style::{Colored, StyledContent, Stylize};
+use std::{
+    io::{self, IsTerminal},
+    path::{Path, PathBuf},
+    sync::LazyLock,
+};
+
+static COLOR: LazyLock<bool> = LazyLock::new(|| {
+    let color = match CLAP_ARGS.get_one::<String>("color").map(String::as_str) {
+        Some("always") => true,
+        Some("never") => false,
+        _ => !utils::no_color() && io::stdout().is_terminal(),
+    };
+
+    // crossterm leaves out the colors on its own when `NO_COLOR` is set
+    Colored::set_ansi_color_disabled(!color);
+
+    color
+});
+
+/// Returns `content` with its styling, or without it if the output shouldn't be colored as
+/// decided by the `--color` flag.
+fn paint<D: std::fmt::Display>(content: StyledContent<D>) -> String {
+    if *COLOR {
+        content.to_string()
+    } else {
+        content.content().to_string()
+    }
+}
 
 pub fn handle_subcommands(app: &mut App) -> bool {
     let res = match CLAP_ARGS.subcommand() {
+        Some((name, args)) if CLAP_ARGS.get_flag("read_only") && modifies_saves(name, args) => Err(
+            anyhow::anyhow!("The {name} subcommand is disabled in read-only mode."),
+        ),
         Some(("list", args)) => handle_list_subcommand(app, args),
         Some(("load", args)) => handle_load_subcommand(app, args),
         Some(("import", args)) => handle_import_subcommand(app, args),
         Some(("rename", args)) => handle_rename_subcommand(app, args),
+        Some(("mark", args)) => handle_mark_subcommand(app, args),
         Some(("delete", args)) => handle_delete_subcommand(app, args),
         Some(("game", args)) => handle_game_subcommand(app, args),
         Some(("profile", args)) => handle_profile_subcommand(app, args),
@@ -30,13 +64,191 @@ pub fn handle_subcommands(app: &mut App) -> bool {
     true
 }
 
-pub fn handle_list_subcommand(app: &mut App, _args: &ArgMatches) -> Result<()> {
+/// Returns true if the subcommand `name` modifies the saves, the games or the profiles, which
+/// every subcommand except listing does.
+fn modifies_saves(name: &str, args: &ArgMatches) -> bool {
+    match name {
+        "load" | "import" | "rename" | "mark" | "delete" => true,
+        "game" | "profile" => args.subcommand_name().is_some_and(|name| name != "list"),
+        _ => false,
+    }
+}
+
+/// Handles the `keys` subcommand. It is handled before the app is created as it only needs the
+/// configuration.
+pub fn handle_keys_subcommand() -> bool {
+    if CLAP_ARGS.subcommand_matches("keys").is_none() {
+        return false;
+    }
+
+    let print_section = |title: &str, entries: &[(String, &str)]| {
+        println!("{}", paint(title.bold()));
+
+        for (keys, desc) in entries {
+            if keys.trim().is_empty() {
+                println!(
+                    "  {}{desc}",
+                    paint(format!("{:16}", "(unbound)").dark_grey())
+                );
+            } else {
+                println!("  {}{desc}", paint(keys.as_str().yellow()));
+            }
+        }
+    };
+
+    print_section("General", &Bindings::general_without_tree());
+
+    let tree = Bindings::tree();
+    if !tree.is_empty() {
+        println!();
+        print_section(Section::Tree.title(), &tree);
+    }
+
+    let bindings = Bindings::default();
+
+    for section in [
+        Section::GameSelection,
+        Section::ProfileSelection,
+        Section::Confirmation,
+        Section::Help,
+    ] {
+        println!();
+        print_section(section.title(), &bindings.section(section));
+    }
+
+    let key_bindings = KEY_BINDINGS.get();
+    let mut games = key_bindings.game.keys().collect::<Vec<_>>();
+    games.sort();
+
+    for game in games {
+        println!();
+        print_section(&format!("Game: {game}"), &Bindings::game(game));
+    }
+
+    true
+}
+
+/// Handles the `doctor` subcommand. It is handled before the app is created so that a broken
+/// configuration file can be reported instead of aborting.
+pub fn handle_doctor_subcommand() -> bool {
+    if CLAP_ARGS.subcommand_matches("doctor").is_none() {
+        return false;
+    }
+
+    let config_status = match config::config_path() {
+        Ok(Some(path)) if !path.exists() => format!("{} (not found)", path.display()),
+        Ok(Some(path)) => match Config::new() {
+            Ok(_) => format!("{} (ok)", path.display()),
+            Err(e) => format!("{} ({})", path.display(), paint(format!("{e:#}").red())),
+        },
+        Ok(None) => String::from("ignored"),
+        Err(e) => paint(format!("{e:#}").red()),
+    };
+
+    // the configuration is already validated, don't let it abort the rest of the report
+    SKIP_CONFIG.call_once(|| {});
+
+    let path_or_error = |path: anyhow::Result<PathBuf>| match path {
+        Ok(path) => path.display().to_string(),
+        Err(e) => paint(format!("{e:#}").red()),
+    };
+
+    println!(
+        "{} {}",
+        paint(env!("CARGO_PKG_NAME").bold()),
+        env!("CARGO_PKG_VERSION")
+    );
+    println!(
+        "{:18}{}",
+        "State directory:",
+        path_or_error(utils::get_state_dir())
+    );
+    println!(
+        "{:18}{}",
+        "Config directory:",
+        path_or_error(utils::get_config_dir())
+    );
+    println!("{:18}{}", "Config file:", config_status);
+
+    match read_games() {
+        Ok(games) => {
+            let active_game = get_active_game().ok();
+            println!("\n{} ({})", paint("Games".bold()), games.len());
+
+            for game in &games {
+                let profile_count = game.read_profiles().map_or(0, |profiles| profiles.len());
+                let savefile_paths = game.read_savefile_paths();
+                let savefile = if savefile_paths.is_empty() {
+                    paint("not set".yellow())
+                } else {
+                    (savefile_paths.iter())
+                        .map(|path| {
+                            if path.exists() {
+                                path.display().to_string()
+                            } else {
+                                format!("{} {}", path.display(), paint("(missing)".yellow()))
+                            }
+                        })
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                };
+
+                println!(
+                    "  {}{}: {profile_count} profile(s), savefile: {savefile}",
+                    game.name(),
+                    if active_game.as_deref() == Some(&game.name()) {
+                        paint(" (*)".yellow().bold())
+                    } else {
+                        String::new()
+                    },
+                );
+            }
+        }
+        Err(e) => println!(
+            "\n{}: {}",
+            paint("Games".bold()),
+            paint(format!("{e:#}").red())
+        ),
+    }
+
+    println!("\n{}", paint("Presets".bold()));
+
+    for preset in creation::presets() {
+        match preset.get_savefile_locations() {
+            Ok(paths) if !paths.is_empty() => {
+                println!("  {preset}:");
+                for path in paths {
+                    println!("    {path}");
+                }
+            }
+            _ => println!("  {preset}: {}", paint("not found".dark_grey())),
+        }
+    }
+
+    true
+}
+
+pub fn handle_list_subcommand(app: &mut App, args: &ArgMatches) -> Result<()> {
+    let template = (args.get_one::<String>("format"))
+        .map(|format| Template::try_from(format.as_str()))
+        .transpose()?;
+
     app.open_all_folds();
 
     let Some(profile) = app.games.get_profile() else {
         return Err(anyhow::anyhow!("No Profile is selected"));
     };
 
+    if let Some(template) = template {
+        let entries = &profile.entries;
+
+        for id in entries.descendants(NodeId::root()).skip(1) {
+            println!("{}", template.expand(profile, id));
+        }
+
+        return Ok(());
+    }
+
     let mut tree_state = TreeState::default();
     let entries = &profile.entries;
 
@@ -47,10 +259,10 @@ pub fn handle_list_subcommand(app: &mut App, _args: &ArgMatches) -> Result<()> {
     for item in Tree::from(entries).items {
         let spans = &item.content.iter().next().unwrap().spans;
 
-        print!("{}", spans[0].content.dark_grey());
+        print!("{}", paint(spans[0].content.dark_grey()));
         print!("{}{}", spans[1], spans[2]);
         if let Some(span) = spans.get(3) {
-            println!("{}", span.content.yellow());
+            println!("{}", paint(span.content.yellow()));
         } else {
             println!();
         }
@@ -60,12 +272,21 @@ pub fn handle_list_subcommand(app: &mut App, _args: &ArgMatches) -> Result<()> {
 }
 
 pub fn handle_load_subcommand(app: &mut App, args: &ArgMatches) -> Result<()> {
+    app.backup_on_load = args.get_flag("backup");
+
+    if args.get_flag("stdin") {
+        return for_each_stdin_line(app, |app, line| {
+            let path = app.games.get_profile().unwrap().abs_path_to(line);
+            app.load_save_file(&path, true)
+        });
+    }
+
     if let Some(path) = get_entry_path(args, app)? {
         app.load_save_file(&path, true)?;
-    } else if !any_args(args) {
-        app.load_active_save_file();
     } else if args.get_flag("random") {
         app.load_random_save_file();
+    } else if !args.get_flag("fuzzy") {
+        app.load_active_save_file();
     } else {
         std::process::exit(1)
     }
@@ -88,12 +309,50 @@ pub fn handle_import_subcommand(app: &mut App, _args: &ArgMatches) -> Result<()>
 }
 
 fn handle_rename_subcommand(app: &mut App, args: &ArgMatches) -> Result<()> {
+    if args.get_flag("stdin") {
+        return for_each_stdin_line(app, |app, line| {
+            let (relative_path, new_name) = line
+                .split_once('\t')
+                .context("Expected RELATIVE_PATH<TAB>NEW_NAME.")?;
+            let entry_path = app.games.get_profile().unwrap().abs_path_to(relative_path);
+            rename_entry(&entry_path, new_name)
+        });
+    }
+
     if let Some(entry_path) = get_entry_path(args, app)? {
-        let new_name = args.get_one::<String>("new_name").unwrap();
-        let mut new_path = entry_path.clone();
-        new_path.set_file_name(new_name);
+        rename_entry(&entry_path, args.get_one::<String>("new_name").unwrap())?;
+    } else {
+        std::process::exit(1)
+    }
+
+    Ok(())
+}
+
+fn rename_entry(entry_path: &Path, new_name: &str) -> Result<()> {
+    let mut new_path = entry_path.to_owned();
+    new_path.set_file_name(new_name);
 
-        utils::rename(&entry_path, &new_path)?;
+    utils::rename(entry_path, &new_path)
+}
+
+fn handle_mark_subcommand(app: &mut App, args: &ArgMatches) -> Result<()> {
+    if let Some(path) = get_entry_path(args, app)? {
+        let game = app.games.get_game_unchecked();
+        let profile = game.get_profile().unwrap();
+
+        // an absolute path or `..` can lead out of the profile the path is joined to
+        let path = (path.canonicalize().ok())
+            .zip(profile.path.canonicalize().ok())
+            .and_then(|(path, profile_path)| {
+                Some(profile.abs_path_to(path.strip_prefix(profile_path).ok()?))
+            })
+            .filter(|path| *path != profile.path && game.is_save(path))
+            .context("There is no such save in the profile.")?;
+
+        app.games
+            .get_profile_mut()
+            .unwrap()
+            .update_active_save_file(&path)?;
     } else {
         std::process::exit(1)
     }
@@ -102,12 +361,15 @@ fn handle_rename_subcommand(app: &mut App, args: &ArgMatches) -> Result<()> {
 }
 
 fn handle_delete_subcommand(app: &mut App, args: &ArgMatches) -> Result<()> {
+    if args.get_flag("stdin") {
+        return for_each_stdin_line(app, |app, line| {
+            let path = app.games.get_profile().unwrap().abs_path_to(line);
+            delete_entry(app, &path)
+        });
+    }
+
     if let Some(path) = get_entry_path(args, app)? {
-        let entries = app.games.get_entries().unwrap();
-        let id = entries
-            .find_by_path(&path)
-            .context("There is no such entry.")?;
-        entries[id].delete()?;
+        delete_entry(app, &path)?;
     } else {
         std::process::exit(1)
     }
@@ -115,6 +377,14 @@ fn handle_delete_subcommand(app: &mut App, args: &ArgMatches) -> Result<()> {
     Ok(())
 }
 
+fn delete_entry(app: &App, path: &Path) -> Result<()> {
+    let entries = app.games.get_entries().unwrap();
+    let id = entries
+        .find_by_path(path)
+        .context("There is no such entry.")?;
+    entries[id].delete()
+}
+
 pub fn handle_game_subcommand(app: &mut App, args: &ArgMatches) -> Result<()> {
     let games = &mut app.games;
 
@@ -123,7 +393,10 @@ pub fn handle_game_subcommand(app: &mut App, args: &ArgMatches) -> Result<()> {
             Games::create_game(
                 &mut app.games,
                 args.get_one::<String>("game_name").unwrap(),
-                args.get_one::<PathBuf>("savefile_path").unwrap(),
+                args.get_many::<PathBuf>("savefile_path")
+                    .unwrap()
+                    .cloned()
+                    .collect(),
             )?;
         }
         Some(("delete", args)) => {
@@ -132,7 +405,11 @@ pub fn handle_game_subcommand(app: &mut App, args: &ArgMatches) -> Result<()> {
         }
         Some(("rename", args)) => {
             select_game_by_idx_or_name(games, args)?;
-            games.rename_selected_game(args.get_one::<String>("new_name").unwrap())?;
+            let new_name = args.get_one::<String>("new_name").unwrap();
+            let path = games.inner.get_selected().unwrap().path.clone();
+            games.rename_selected_game(new_name)?;
+            // there is no watcher to update the active game after the rename
+            games.on_rename(&path, &path.with_file_name(new_name))?;
         }
         Some(("list", args)) => {
             for (idx, profile) in games.inner.items.iter().enumerate() {
@@ -141,14 +418,14 @@ pub fn handle_game_subcommand(app: &mut App, args: &ArgMatches) -> Result<()> {
                     if args.get_flag("no_index") {
                         String::new()
                     } else {
-                        format!("[{idx}] ").bold().to_string()
+                        paint(format!("[{idx}] ").bold())
                     },
                     profile.name(),
                     if games
                         .active_game
                         .is_some_and(|active_idx| active_idx == idx)
                     {
-                        " (*)".yellow().bold().to_string()
+                        paint(" (*)".yellow().bold())
                     } else {
                         String::new()
                     },
@@ -178,7 +455,11 @@ pub fn handle_profile_subcommand(app: &mut App, args: &ArgMatches) -> Result<()>
         }
         Some(("rename", args)) => {
             select_profile_by_idx_or_name(game, args)?;
-            game.rename_selected_profile(args.get_one::<String>("new_name").unwrap())?;
+            let new_name = args.get_one::<String>("new_name").unwrap();
+            let path = game.profiles.get_selected().unwrap().path.clone();
+            game.rename_selected_profile(new_name)?;
+            // there is no watcher to update the active profile after the rename
+            game.on_rename(&path, &path.with_file_name(new_name))?;
         }
         Some(("list", args)) => {
             for (idx, profile) in game.profiles.items.iter().enumerate() {
@@ -187,14 +468,14 @@ pub fn handle_profile_subcommand(app: &mut App, args: &ArgMatches) -> Result<()>
                     if args.get_flag("no_index") {
                         String::new()
                     } else {
-                        format!("[{idx}] ").bold().to_string()
+                        paint(format!("[{idx}] ").bold())
                     },
                     profile.name(),
                     if game
                         .active_profile
                         .is_some_and(|active_idx| active_idx == idx)
                     {
-                        " (*)".yellow().bold().to_string()
+                        paint(" (*)".yellow().bold())
                     } else {
                         String::new()
                     },
@@ -211,10 +492,36 @@ pub fn handle_profile_subcommand(app: &mut App, args: &ArgMatches) -> Result<()>
     Ok(())
 }
 
-fn any_args(args: &ArgMatches) -> bool {
-    args.ids()
-        .filter_map(|id| args.value_source(id.as_str()))
-        .any(|value| value == ValueSource::CommandLine)
+/// Runs `f` for each non-empty line of stdin, reporting the result of each line and failing if
+/// any of them failed.
+fn for_each_stdin_line(
+    app: &mut App,
+    mut f: impl FnMut(&mut App, &str) -> Result<()>,
+) -> Result<()> {
+    app.games.get_profile().context("No profile is selected.")?;
+
+    let mut failed = 0;
+
+    for line in std::io::stdin().lines() {
+        let line = line?;
+        let line = line.trim_end_matches('\r');
+
+        if line.is_empty() {
+            continue;
+        }
+
+        match f(app, line) {
+            Ok(()) => println!("{} {line}", paint("ok".green())),
+            Err(e) => {
+                eprintln!("{} {line}: {e:#}", paint("failed".red()));
+                failed += 1;
+            }
+        }
+    }
+
+    ensure!(failed == 0, "{failed} line(s) failed.");
+
+    Ok(())
 }
 
 fn get_entry_path(args: &ArgMatches, app: &mut App) -> Result<Option<PathBuf>> {
@@ -1,10 +1,11 @@
 use clap::{Arg, ArgAction, ArgMatches, Command, builder::ValueParser};
-pub use handlers::handle_subcommands;
+pub use handlers::{handle_doctor_subcommand, handle_keys_subcommand, handle_subcommands};
 use std::{env, sync::LazyLock};
 
 mod commands;
 mod completion;
 mod handlers;
+mod template;
 
 pub static CLAP_ARGS: LazyLock<ArgMatches> = LazyLock::new(get_matches);
 
@@ -27,9 +28,33 @@ pub fn build_command() -> Command {
                 .conflicts_with("config")
                 .action(ArgAction::SetTrue),
         )
+        .arg(
+            Arg::new("read_only")
+                .long("read-only")
+                .help("Disable the commands and subcommands that modify the save files")
+                .action(ArgAction::SetTrue)
+                .global(true),
+        )
+        .arg(
+            Arg::new("log")
+                .long("log")
+                .help("Append operations and errors to the log file")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("color")
+                .long("color")
+                .help("When to color the output of the subcommands")
+                .value_parser(["always", "auto", "never"])
+                .default_value("auto")
+                .value_name("WHEN")
+                .global(true),
+        )
         .subcommands(commands::create_entry_subcommands())
         .subcommand(commands::create_game_subcommand())
         .subcommand(commands::create_profile_subcommand())
+        .subcommand(commands::create_doctor_subcommand())
+        .subcommand(commands::create_keys_subcommand())
 }
 
 pub fn get_matches() -> ArgMatches {
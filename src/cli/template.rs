@@ -0,0 +1,154 @@
+use crate::{game::profile::Profile, tree::NodeId};
+use anyhow::{Result, bail};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, PartialEq)]
+enum Placeholder {
+    Path,
+    Name,
+    IsFolder,
+    Active,
+    Size,
+    Mtime,
+    Created,
+}
+
+impl TryFrom<&str> for Placeholder {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Ok(match value {
+            "path" => Self::Path,
+            "name" => Self::Name,
+            "is_folder" => Self::IsFolder,
+            "active" => Self::Active,
+            "size" => Self::Size,
+            "mtime" => Self::Mtime,
+            "created" => Self::Created,
+            _ => bail!(
+                "Unknown placeholder {{{value}}}. Available placeholders are {{path}}, {{name}}, \
+                 {{is_folder}}, {{active}}, {{size}}, {{mtime}} and {{created}}."
+            ),
+        })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Segment {
+    Text(String),
+    Placeholder(Placeholder),
+}
+
+/// Template for printing an entry per line. `\t`, `\n` and `\\` are unescaped so that the
+/// separators can be given without the help of the shell.
+pub struct Template(Vec<Segment>);
+
+impl TryFrom<&str> for Template {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let mut segments = Vec::new();
+        let mut text = String::new();
+        let mut chars = value.chars();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => match chars.next() {
+                    Some('t') => text.push('\t'),
+                    Some('n') => text.push('\n'),
+                    Some('\\') => text.push('\\'),
+                    Some(c) => text.extend(['\\', c]),
+                    None => text.push('\\'),
+                },
+                '{' => {
+                    let mut name = String::new();
+
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => name.push(c),
+                            None => bail!("Unclosed placeholder {{{name} in the format."),
+                        }
+                    }
+
+                    if !text.is_empty() {
+                        segments.push(Segment::Text(std::mem::take(&mut text)));
+                    }
+                    segments.push(Segment::Placeholder(Placeholder::try_from(name.as_str())?));
+                }
+                c => text.push(c),
+            }
+        }
+
+        if !text.is_empty() {
+            segments.push(Segment::Text(text));
+        }
+
+        Ok(Self(segments))
+    }
+}
+
+impl Template {
+    /// Expands the placeholders for the entry with the given `id` in the profile.
+    pub fn expand(&self, profile: &Profile, id: NodeId) -> String {
+        let entries = &profile.entries;
+        let entry = &entries[id];
+
+        self.0
+            .iter()
+            .map(|segment| match segment {
+                Segment::Text(text) => text.clone(),
+                Segment::Placeholder(placeholder) => match placeholder {
+                    Placeholder::Path => profile.rel_path_to(&entry.path),
+                    Placeholder::Name => entry.name().to_string_lossy().into_owned(),
+                    Placeholder::IsFolder => entry.is_folder().to_string(),
+                    Placeholder::Active => profile
+                        .get_active_save_file()
+                        .is_some_and(|path| path == entry.path)
+                        .to_string(),
+                    Placeholder::Size => (entries.descendants(id))
+                        .filter(|id| entries[*id].is_file())
+                        .filter_map(|id| entries[id].metadata())
+                        .map(|metadata| metadata.len)
+                        .sum::<u64>()
+                        .to_string(),
+                    Placeholder::Mtime => {
+                        unix_secs(entry.metadata().and_then(|metadata| metadata.modified))
+                    }
+                    Placeholder::Created => {
+                        unix_secs(entry.metadata().and_then(|metadata| metadata.created))
+                    }
+                },
+            })
+            .collect()
+    }
+}
+
+fn unix_secs(time: Option<SystemTime>) -> String {
+    time.and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |duration| duration.as_secs())
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Placeholder, Segment, Template};
+
+    #[test]
+    fn parse() {
+        let template = Template::try_from("{path}\\t{active}!{created}").unwrap();
+        assert_eq!(
+            template.0,
+            [
+                Segment::Placeholder(Placeholder::Path),
+                Segment::Text(String::from("\t")),
+                Segment::Placeholder(Placeholder::Active),
+                Segment::Text(String::from("!")),
+                Segment::Placeholder(Placeholder::Created),
+            ]
+        );
+
+        assert!(Template::try_from("{size").is_err());
+        assert!(Template::try_from("{unknown}").is_err());
+    }
+}
@@ -13,32 +13,98 @@ pub enum Command {
     LoadRandomSaveFile,
     LoadActiveSaveFile,
     MarkSaveFile,
+    UnmarkActive,
     ImportSaveFile,
     ImportSaveFileTopLevel,
     ReplaceSaveFile,
+    ReplaceWithActiveSaveFile,
     DeleteFile,
     CreateFolder,
     CreateFolderTopLevel,
     Rename,
+    BulkRename,
+    ToggleExtension,
     MoveEntries,
     MoveEntriesTopLevel,
+    YankEntries,
+    CutEntries,
+    PasteEntries,
+    MergeFolders,
+    PruneEmptyFolders,
     MoveUp,
     MoveDown,
+    ExpandOne,
     OpenAllFolds,
     CloseAllFolds,
+    FocusFolder,
+    ToggleFlatView,
     OpenGameWindow,
     OpenProfileWindow,
+    NextGame,
+    PreviousGame,
+    NextProfile,
+    PreviousProfile,
     ToggleHelp,
     EnterSearch,
     RepeatLastSearch,
     RepeatLastSearchBackward,
+    EnterFilter,
+    ExportMatches,
     OpenFuzzyFinder,
     OpenFuzzyFinderGlobal,
+    SearchContents,
     MarkEntry,
+    MarkSearchMatches,
+    PinEntry,
+    OpenPinnedPicker,
+    DiffMarked,
+    SwapContents,
+    BroadcastToProfiles,
+    ToggleEventLog,
+    ShowDiskUsage,
+    OpenShell,
+    EditConfig,
+    ReloadConfig,
+    ToggleAbsolutePaths,
+    ToggleReadOnly,
     Reset,
     Quit,
 }
 
+impl Command {
+    /// Returns `true` if the command modifies the save files or the game's live save.
+    pub fn is_mutating(self) -> bool {
+        matches!(
+            self,
+            Command::LoadSaveFile
+                | Command::LoadRandomSaveFile
+                | Command::LoadActiveSaveFile
+                | Command::MarkSaveFile
+                | Command::UnmarkActive
+                | Command::PinEntry
+                | Command::ImportSaveFile
+                | Command::ImportSaveFileTopLevel
+                | Command::ReplaceSaveFile
+                | Command::ReplaceWithActiveSaveFile
+                | Command::DeleteFile
+                | Command::CreateFolder
+                | Command::CreateFolderTopLevel
+                | Command::Rename
+                | Command::BulkRename
+                | Command::ToggleExtension
+                | Command::MoveEntries
+                | Command::MoveEntriesTopLevel
+                | Command::PasteEntries
+                | Command::MergeFolders
+                | Command::PruneEmptyFolders
+                | Command::SwapContents
+                | Command::BroadcastToProfiles
+                | Command::MoveUp
+                | Command::MoveDown
+        )
+    }
+}
+
 impl TryFrom<&str> for Command {
     type Error = anyhow::Error;
 
@@ -56,28 +122,60 @@ impl TryFrom<&str> for Command {
             "load_save_file" => Command::LoadSaveFile,
             "load_active_save_file" => Command::LoadActiveSaveFile,
             "mark_save_file" => Command::MarkSaveFile,
+            "unmark_active" => Command::UnmarkActive,
             "import_save_file" => Command::ImportSaveFile,
             "import_save_file_top_level" => Command::ImportSaveFileTopLevel,
             "replace_save_file" => Command::ReplaceSaveFile,
+            "replace_with_active_save_file" => Command::ReplaceWithActiveSaveFile,
             "delete_file" => Command::DeleteFile,
             "create_folder" => Command::CreateFolder,
             "create_folder_top_level" => Command::CreateFolderTopLevel,
             "rename" => Command::Rename,
+            "bulk_rename" => Command::BulkRename,
+            "toggle_extension" => Command::ToggleExtension,
             "move_entries" => Command::MoveEntries,
             "move_entries_top_level" => Command::MoveEntriesTopLevel,
+            "yank_entries" => Command::YankEntries,
+            "cut_entries" => Command::CutEntries,
+            "paste_entries" => Command::PasteEntries,
+            "merge_folders" => Command::MergeFolders,
+            "prune_empty_folders" => Command::PruneEmptyFolders,
             "move_up" => Command::MoveUp,
             "move_down" => Command::MoveDown,
+            "expand_one" => Command::ExpandOne,
             "open_all_folds" => Command::OpenAllFolds,
             "close_all_folds" => Command::CloseAllFolds,
+            "focus_folder" => Command::FocusFolder,
+            "toggle_flat_view" => Command::ToggleFlatView,
             "open_game_window" => Command::OpenGameWindow,
             "open_profile_window" => Command::OpenProfileWindow,
+            "next_game" => Command::NextGame,
+            "previous_game" => Command::PreviousGame,
+            "next_profile" => Command::NextProfile,
+            "previous_profile" => Command::PreviousProfile,
             "toggle_help" => Command::ToggleHelp,
             "enter_search" => Command::EnterSearch,
             "repeat_last_search" => Command::RepeatLastSearch,
             "repeat_last_search_backward" => Command::RepeatLastSearchBackward,
+            "enter_filter" => Command::EnterFilter,
+            "export_matches" => Command::ExportMatches,
             "open_fuzzy_finder" => Command::OpenFuzzyFinder,
             "open_fuzzy_finder_global" => Command::OpenFuzzyFinderGlobal,
+            "search_contents" => Command::SearchContents,
             "mark_entry" => Command::MarkEntry,
+            "mark_search_matches" => Command::MarkSearchMatches,
+            "pin_entry" => Command::PinEntry,
+            "open_pinned_picker" => Command::OpenPinnedPicker,
+            "diff_marked" => Command::DiffMarked,
+            "swap_contents" => Command::SwapContents,
+            "broadcast_to_profiles" => Command::BroadcastToProfiles,
+            "toggle_event_log" => Command::ToggleEventLog,
+            "show_disk_usage" => Command::ShowDiskUsage,
+            "open_shell" => Command::OpenShell,
+            "edit_config" => Command::EditConfig,
+            "reload_config" => Command::ReloadConfig,
+            "toggle_absolute_paths" => Command::ToggleAbsolutePaths,
+            "toggle_read_only" => Command::ToggleReadOnly,
             "reset" => Command::Reset,
             "quit" => Command::Quit,
             _ => anyhow::bail!("\"{}\" is an invalid command", command),
@@ -97,6 +195,19 @@ pub enum GameSelectionCommand {
     Abort,
 }
 
+impl GameSelectionCommand {
+    /// Returns `true` if the command modifies the games.
+    pub fn is_mutating(self) -> bool {
+        matches!(
+            self,
+            GameSelectionCommand::Create
+                | GameSelectionCommand::Rename
+                | GameSelectionCommand::Delete
+                | GameSelectionCommand::SetSavefile
+        )
+    }
+}
+
 impl TryFrom<&str> for GameSelectionCommand {
     type Error = anyhow::Error;
 
@@ -119,11 +230,25 @@ impl TryFrom<&str> for GameSelectionCommand {
 pub enum ProfileSelectionCommand {
     Create,
     Rename,
+    EditNote,
     Delete,
     Select,
     Abort,
 }
 
+impl ProfileSelectionCommand {
+    /// Returns `true` if the command modifies the profiles.
+    pub fn is_mutating(self) -> bool {
+        matches!(
+            self,
+            ProfileSelectionCommand::Create
+                | ProfileSelectionCommand::Rename
+                | ProfileSelectionCommand::EditNote
+                | ProfileSelectionCommand::Delete
+        )
+    }
+}
+
 impl TryFrom<&str> for ProfileSelectionCommand {
     type Error = anyhow::Error;
 
@@ -131,6 +256,7 @@ impl TryFrom<&str> for ProfileSelectionCommand {
         let command = match command {
             "create" => ProfileSelectionCommand::Create,
             "rename" => ProfileSelectionCommand::Rename,
+            "edit_note" => ProfileSelectionCommand::EditNote,
             "delete" => ProfileSelectionCommand::Delete,
             "select" => ProfileSelectionCommand::Select,
             "abort" => ProfileSelectionCommand::Abort,
@@ -147,6 +273,8 @@ pub enum HelpCommand {
     ScrollDown,
     GoToTop,
     GoToBottom,
+    Grow,
+    Shrink,
     Abort,
 }
 
@@ -159,6 +287,8 @@ impl TryFrom<&str> for HelpCommand {
             "scroll_down" => HelpCommand::ScrollDown,
             "go_to_top" => HelpCommand::GoToTop,
             "go_to_bottom" => HelpCommand::GoToBottom,
+            "grow" => HelpCommand::Grow,
+            "shrink" => HelpCommand::Shrink,
             "abort" => HelpCommand::Abort,
             _ => anyhow::bail!("\"{}\" is an invalid command", command),
         };
@@ -15,6 +15,8 @@ use std::{
 pub struct UserKeyBindings {
     #[serde(flatten)]
     general: Option<HashMap<String, String>>,
+    tree: Option<HashMap<String, String>>,
+    game: Option<HashMap<String, HashMap<String, String>>>,
     game_selection: Option<HashMap<String, String>>,
     profile_selection: Option<HashMap<String, String>>,
     help: Option<HashMap<String, String>>,
@@ -76,6 +78,11 @@ fn parse_binding(binding: &str) -> Result<KeyEvent> {
 #[derive(PartialEq, Eq, Debug)]
 pub struct KeyBindings {
     pub general: IndexMap<KeyEvent, Command>,
+    /// Bindings that are only active in the tree. They take precedence over the general ones.
+    pub tree: IndexMap<KeyEvent, Command>,
+    /// Bindings that are only active in the tree while the game with the given name is
+    /// selected. They take precedence over the tree and the general ones.
+    pub game: HashMap<String, IndexMap<KeyEvent, Command>>,
     pub game_selection: IndexMap<KeyEvent, GameSelectionCommand>,
     pub profile_selection: IndexMap<KeyEvent, ProfileSelectionCommand>,
     pub help: IndexMap<KeyEvent, HelpCommand>,
@@ -115,29 +122,61 @@ impl Default for KeyBindings {
         insert_binding!(general, "shift-alt-f", Command::LoadRandomSaveFile);
         insert_binding!(general, "ctrl-f", Command::LoadActiveSaveFile);
         insert_binding!(general, "F", Command::MarkSaveFile);
+        insert_binding!(general, "u", Command::UnmarkActive);
         insert_binding!(general, "i", Command::ImportSaveFile);
         insert_binding!(general, "I", Command::ImportSaveFileTopLevel);
         insert_binding!(general, "R", Command::ReplaceSaveFile);
+        insert_binding!(general, "alt-r", Command::ReplaceWithActiveSaveFile);
         insert_binding!(general, "d", Command::DeleteFile);
         insert_binding!(general, "c", Command::CreateFolder);
         insert_binding!(general, "C", Command::CreateFolderTopLevel);
         insert_binding!(general, "r", Command::Rename);
+        insert_binding!(general, "b", Command::BulkRename);
+        insert_binding!(general, ".", Command::ToggleExtension);
         insert_binding!(general, "p", Command::MoveEntries);
         insert_binding!(general, "P", Command::MoveEntriesTopLevel);
+        insert_binding!(general, "y", Command::YankEntries);
+        insert_binding!(general, "x", Command::CutEntries);
+        insert_binding!(general, "alt-p", Command::PasteEntries);
+        insert_binding!(general, "E", Command::MergeFolders);
+        insert_binding!(general, "alt-d", Command::PruneEmptyFolders);
         insert_binding!(general, "M", Command::MoveUp);
         insert_binding!(general, "m", Command::MoveDown);
+        insert_binding!(general, "A", Command::ExpandOne);
         insert_binding!(general, "a", Command::OpenAllFolds);
         insert_binding!(general, "z", Command::CloseAllFolds);
+        insert_binding!(general, "Z", Command::FocusFolder);
+        insert_binding!(general, "v", Command::ToggleFlatView);
         insert_binding!(general, "W", Command::OpenGameWindow);
         insert_binding!(general, "w", Command::OpenProfileWindow);
+        insert_binding!(general, "}", Command::NextGame);
+        insert_binding!(general, "{", Command::PreviousGame);
+        insert_binding!(general, "]", Command::NextProfile);
+        insert_binding!(general, "[", Command::PreviousProfile);
         insert_binding!(general, "ctrl-h", Command::ToggleHelp);
         insert_binding!(general, "f1", Command::ToggleHelp);
         insert_binding!(general, "/", Command::EnterSearch);
         insert_binding!(general, "n", Command::RepeatLastSearch);
         insert_binding!(general, "N", Command::RepeatLastSearchBackward);
+        insert_binding!(general, "|", Command::EnterFilter);
+        insert_binding!(general, "e", Command::ExportMatches);
         insert_binding!(general, "s", Command::OpenFuzzyFinder);
         insert_binding!(general, "S", Command::OpenFuzzyFinderGlobal);
+        insert_binding!(general, "ctrl-s", Command::SearchContents);
         insert_binding!(general, "space", Command::MarkEntry);
+        insert_binding!(general, "alt-space", Command::MarkSearchMatches);
+        insert_binding!(general, "t", Command::PinEntry);
+        insert_binding!(general, "T", Command::OpenPinnedPicker);
+        insert_binding!(general, "D", Command::DiffMarked);
+        insert_binding!(general, "X", Command::SwapContents);
+        insert_binding!(general, "B", Command::BroadcastToProfiles);
+        insert_binding!(general, "L", Command::ToggleEventLog);
+        insert_binding!(general, "U", Command::ShowDiskUsage);
+        insert_binding!(general, "!", Command::OpenShell);
+        insert_binding!(general, "ctrl-e", Command::EditConfig);
+        insert_binding!(general, "alt-e", Command::ReloadConfig);
+        insert_binding!(general, "ctrl-p", Command::ToggleAbsolutePaths);
+        insert_binding!(general, "ctrl-r", Command::ToggleReadOnly);
         insert_binding!(general, "esc", Command::Reset);
         insert_binding!(general, "q", Command::Quit);
         insert_binding!(general, "ctrl-c", Command::Quit);
@@ -151,6 +190,7 @@ impl Default for KeyBindings {
 
         insert_binding!(profile_selection, "c", ProfileSelectionCommand::Create);
         insert_binding!(profile_selection, "r", ProfileSelectionCommand::Rename);
+        insert_binding!(profile_selection, "n", ProfileSelectionCommand::EditNote);
         insert_binding!(profile_selection, "d", ProfileSelectionCommand::Delete);
         insert_binding!(profile_selection, "enter", ProfileSelectionCommand::Select);
         insert_binding!(profile_selection, "escape", ProfileSelectionCommand::Abort);
@@ -159,6 +199,8 @@ impl Default for KeyBindings {
         insert_binding!(help, "ctrl-e", HelpCommand::ScrollDown);
         insert_binding!(help, "g", HelpCommand::GoToTop);
         insert_binding!(help, "G", HelpCommand::GoToBottom);
+        insert_binding!(help, "alt-up", HelpCommand::Grow);
+        insert_binding!(help, "alt-down", HelpCommand::Shrink);
         insert_binding!(help, "esc", HelpCommand::Abort);
 
         insert_binding!(confirmation, "y", ConfirmationCommand::Confirm);
@@ -172,6 +214,8 @@ impl Default for KeyBindings {
 
         Self {
             general,
+            tree: IndexMap::new(),
+            game: HashMap::new(),
             game_selection,
             profile_selection,
             help,
@@ -216,6 +260,14 @@ impl MergeConfig for KeyBindings {
             set_bindings(&mut self.general, &bindings)?;
         }
 
+        if let Some(bindings) = user_key_bindings.tree {
+            set_bindings(&mut self.tree, &bindings)?;
+        }
+
+        for (game, bindings) in user_key_bindings.game.unwrap_or_default() {
+            set_bindings(self.game.entry(game).or_default(), &bindings)?;
+        }
+
         if let Some(bindings) = user_key_bindings.game_selection {
             set_bindings(&mut self.game_selection, &bindings)?;
         }
@@ -236,6 +288,16 @@ impl MergeConfig for KeyBindings {
     }
 }
 
+impl KeyBindings {
+    /// Returns the command bound to `key` in the tree while `game` is selected.
+    pub fn get_in_tree(&self, game: Option<&str>, key: &KeyEvent) -> Option<&Command> {
+        game.and_then(|game| self.game.get(game))
+            .and_then(|bindings| bindings.get(key))
+            .or_else(|| self.tree.get(key))
+            .or_else(|| self.general.get(key))
+    }
+}
+
 impl Deref for KeyBindings {
     type Target = IndexMap<KeyEvent, Command>;
 
@@ -252,7 +314,11 @@ impl DerefMut for KeyBindings {
 
 #[cfg(test)]
 mod tests {
-    use crate::config::{Config, keys::UserKeyBindings, tests::read_example_config};
+    use super::{KeyBindings, parse_binding};
+    use crate::{
+        commands::Command,
+        config::{Config, MergeConfig, keys::UserKeyBindings, tests::read_example_config},
+    };
 
     #[test]
     fn example_up_to_date() {
@@ -261,6 +327,8 @@ mod tests {
 
         let UserKeyBindings {
             general,
+            tree,
+            game,
             game_selection,
             profile_selection,
             help,
@@ -268,6 +336,8 @@ mod tests {
         } = user_config.key_bindings.unwrap();
 
         assert!(general.is_some_and(|keys| keys.len() == default.general.len()));
+        assert!(tree.is_some_and(|keys| keys.len() == default.tree.len()));
+        assert!(game.is_some_and(|games| games.len() == default.game.len()));
         assert!(game_selection.is_some_and(|keys| keys.len() == default.game_selection.len()));
         assert!(
             profile_selection.is_some_and(|keys| keys.len() == default.profile_selection.len())
@@ -275,4 +345,33 @@ mod tests {
         assert!(help.is_some_and(|keys| keys.len() == default.help.len()));
         assert!(confirmation.is_some_and(|keys| keys.len() == default.confirmation.len()));
     }
+
+    #[test]
+    fn game_bindings() {
+        let mut key_bindings = KeyBindings::default();
+        let user_key_bindings = toml::from_str::<UserKeyBindings>(
+            "\"x\" = \"quit\"\n[game.\"Elden Ring\"]\n\"f\" = \"load_active_save_file\"",
+        )
+        .unwrap();
+        key_bindings.merge(user_key_bindings).unwrap();
+
+        let f = parse_binding("f").unwrap();
+
+        assert_eq!(
+            key_bindings.get_in_tree(Some("Elden Ring"), &f),
+            Some(&Command::LoadActiveSaveFile)
+        );
+        assert_eq!(
+            key_bindings.get_in_tree(Some("Sekiro"), &f),
+            Some(&Command::LoadSaveFile)
+        );
+        assert_eq!(
+            key_bindings.get_in_tree(None, &f),
+            Some(&Command::LoadSaveFile)
+        );
+        assert_eq!(
+            key_bindings.get_in_tree(Some("Elden Ring"), &parse_binding("x").unwrap()),
+            Some(&Command::Quit)
+        );
+    }
 }
@@ -7,30 +7,33 @@ use self::{
     options::{Options, UserOptions},
 };
 use crate::{cli::CLAP_ARGS, utils};
-use anyhow::Result;
+use anyhow::{Context, Result, bail};
 use serde::Deserialize;
 use std::{
     fs,
-    path::PathBuf,
-    sync::{LazyLock, Once},
+    ops::Deref,
+    path::{Path, PathBuf},
+    sync::{Arc, LazyLock, Once, RwLock},
 };
 use theme::{Theme, UserTheme};
 
-static CONFIG: LazyLock<Config> = LazyLock::new(|| match Config::new() {
-    Ok(config) => config,
+static CONFIG: LazyLock<RwLock<Arc<Config>>> = LazyLock::new(|| match Config::new() {
+    Ok(config) => RwLock::new(Arc::new(config)),
     Err(e) => {
         eprintln!("{e:?}");
         std::process::exit(1);
     }
 });
-pub static OPTIONS: LazyLock<&Options> = LazyLock::new(|| &CONFIG.options);
-pub static KEY_BINDINGS: LazyLock<&KeyBindings> = LazyLock::new(|| &CONFIG.key_bindings);
-pub static THEME: LazyLock<&Theme> = LazyLock::new(|| &CONFIG.theme);
+pub static OPTIONS: Current<Options> = Current(|config| &config.options);
+pub static KEY_BINDINGS: Current<KeyBindings> = Current(|config| &config.key_bindings);
+pub static THEME: Current<Theme> = Current(|config| &config.theme);
 pub static SKIP_CONFIG: Once = Once::new();
 const CONFIG_FILE: &str = "config.toml";
 
 #[derive(Deserialize)]
 struct UserConfig {
+    /// Files merged before this one, relative to its folder.
+    include: Option<Vec<PathBuf>>,
     #[serde(flatten)]
     options: UserOptions,
     theme: Option<UserTheme>,
@@ -48,28 +51,98 @@ impl Config {
     pub fn new() -> Result<Self> {
         let mut config = Self::default();
 
-        if SKIP_CONFIG.is_completed() {
-            return Ok(config);
+        if !SKIP_CONFIG.is_completed()
+            && let Some(path) = config_path()?.filter(|path| path.is_file())
+        {
+            config.merge_file(&path, &mut Vec::new())?;
         }
 
-        let config_path = (!CLAP_ARGS.get_flag("no_config")).then_some(
-            match CLAP_ARGS.get_one::<PathBuf>("config") {
-                Some(path) => path.to_owned(),
-                None => utils::get_config_dir()?.join(CONFIG_FILE),
-            },
-        );
+        if utils::no_color() {
+            config.theme = Theme::plain();
+        }
+
+        Ok(config)
+    }
 
-        let config_str = config_path.as_ref().map(fs::read_to_string);
+    /// Merges the config file at `path` after the files it includes. `including` holds the
+    /// files whose includes are being merged, to detect include cycles.
+    fn merge_file(&mut self, path: &Path, including: &mut Vec<PathBuf>) -> Result<()> {
+        let path = path
+            .canonicalize()
+            .with_context(|| format!("Couldn't read config file {}", path.display()))?;
 
-        if let Some(Ok(user_config)) = &config_str {
-            let user_config = toml::from_str::<UserConfig>(user_config)?;
-            config.merge(user_config)?;
+        if including.contains(&path) {
+            bail!("Config file {} includes itself", path.display());
         }
 
-        Ok(config)
+        let config_str = fs::read_to_string(&path)?;
+        let mut user_config = toml::from_str::<UserConfig>(&config_str)
+            .with_context(|| format!("Invalid config file {}", path.display()))?;
+
+        if let Some(includes) = user_config.include.take() {
+            let dir = path.parent().unwrap_or(Path::new(""));
+            including.push(path.clone());
+
+            for include in includes {
+                self.merge_file(&dir.join(include), including)?;
+            }
+
+            including.pop();
+        }
+
+        self.merge(user_config)
+    }
+}
+
+/// A part of the current config.
+pub struct Current<T: 'static>(fn(&Config) -> &T);
+
+impl<T> Current<T> {
+    /// Returns the part of the latest loaded config. The snapshot keeps that config alive, so it
+    /// isn't affected by reloads while it is held.
+    pub fn get(&self) -> Snapshot<T> {
+        Snapshot {
+            config: Arc::clone(&CONFIG.read().unwrap()),
+            part: self.0,
+        }
     }
 }
 
+/// A part of a loaded config, returned by [`Current::get`].
+pub struct Snapshot<T: 'static> {
+    config: Arc<Config>,
+    part: fn(&Config) -> &T,
+}
+
+impl<T> Deref for Snapshot<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        (self.part)(&self.config)
+    }
+}
+
+/// Reads the config file again and replaces the current config with it. The current config is
+/// kept if the new one is invalid. Snapshots of the replaced config stay valid until dropped.
+pub fn reload() -> Result<()> {
+    let config = Config::new()?;
+    *CONFIG.write().unwrap() = Arc::new(config);
+
+    Ok(())
+}
+
+/// Returns the path of the configuration file or `None` if it should be ignored.
+pub fn config_path() -> Result<Option<PathBuf>> {
+    if CLAP_ARGS.get_flag("no_config") {
+        return Ok(None);
+    }
+
+    Ok(Some(match CLAP_ARGS.get_one::<PathBuf>("config") {
+        Some(path) => path.to_owned(),
+        None => utils::get_config_dir()?.join(CONFIG_FILE),
+    }))
+}
+
 impl MergeConfig for Config {
     type Other = UserConfig;
 
@@ -96,7 +169,7 @@ trait MergeConfig {
 
 #[cfg(test)]
 mod tests {
-    use super::UserConfig;
+    use super::{Config, UserConfig};
     use std::{fs, path::PathBuf};
 
     pub fn read_example_config() -> UserConfig {
@@ -104,4 +177,40 @@ mod tests {
         let config_str = fs::read_to_string(config_path).unwrap();
         toml::from_str::<UserConfig>(&config_str).unwrap()
     }
+
+    #[test]
+    fn include() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("extra")).unwrap();
+        fs::write(
+            dir.path().join("config.toml"),
+            "include = [\"extra/options.toml\"]\nlog = true",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("extra/options.toml"),
+            "log = false\nshow_counts = true",
+        )
+        .unwrap();
+
+        let mut config = Config::default();
+        config
+            .merge_file(&dir.path().join("config.toml"), &mut Vec::new())
+            .unwrap();
+
+        assert!(config.options.log);
+        assert!(config.options.show_counts);
+
+        fs::write(
+            dir.path().join("extra/options.toml"),
+            "include = [\"../config.toml\"]",
+        )
+        .unwrap();
+
+        assert!(
+            Config::default()
+                .merge_file(&dir.path().join("config.toml"), &mut Vec::new())
+                .is_err()
+        );
+    }
 }
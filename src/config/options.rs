@@ -1,6 +1,7 @@
 use super::MergeConfig;
+use crate::search::Context as SearchContext;
 use anyhow::Result;
-use serde::{Deserialize, de};
+use serde::{Deserialize, Serialize, de};
 use std::collections::HashMap;
 
 #[derive(Deserialize)]
@@ -28,6 +29,176 @@ pub struct RenameOptions {
     pub cursor: RenameCursor,
 }
 
+#[derive(Default, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all(deserialize = "snake_case"))]
+pub enum ListSort {
+    #[default]
+    Name,
+    LastUsed,
+}
+
+#[derive(Default, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all(deserialize = "snake_case"))]
+pub enum StartView {
+    /// The tree of the active profile, or the game list if there isn't one.
+    #[default]
+    Tree,
+    Games,
+    /// The profile list of the active game, or the game list if there isn't one.
+    Profiles,
+}
+
+#[derive(Default, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all(deserialize = "snake_case"))]
+pub enum FuzzyTiebreak {
+    /// Shorter paths first.
+    #[default]
+    Length,
+    /// More recently modified files first, then shorter paths.
+    Recency,
+}
+
+#[derive(Default, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all(deserialize = "snake_case"))]
+pub enum FuzzyEmptyOrder {
+    /// The same order as the matches of a query.
+    #[default]
+    Score,
+    /// More recently modified files first.
+    Recent,
+    /// Alphabetically by path.
+    Path,
+}
+
+#[derive(Default, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all(deserialize = "snake_case"))]
+pub enum AfterDelete {
+    #[default]
+    SelectPrev,
+    SelectNext,
+    /// Selects the folder that contained the deleted entry.
+    Stay,
+}
+
+#[derive(Default, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all(deserialize = "snake_case"))]
+pub enum PathDisplay {
+    #[default]
+    Name,
+    /// Path relative to the profile folder.
+    RelativePath,
+}
+
+/// Line numbers shown at the left of the entries in the tree.
+#[derive(Default, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all(deserialize = "snake_case"))]
+pub enum LineNumbers {
+    #[default]
+    None,
+    /// Position of the entry among the visible entries, starting from 1.
+    Absolute,
+    /// Distance of the entry from the selected entry, which shows its absolute line number.
+    Relative,
+}
+
+/// Action of enter in the tree unless it is bound to a command.
+#[derive(Default, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all(deserialize = "snake_case"))]
+pub enum EnterAction {
+    /// Loads the selected save, or toggles the fold of the selected folder.
+    #[default]
+    Load,
+    /// Toggles the fold of the selected folder, or closes the folder containing the selected
+    /// save.
+    ToggleFold,
+    /// Opens the selected folder and selects its first entry, or loads the selected save.
+    EnterFolder,
+}
+
+/// Whether the matches are searched while typing the pattern in the tree and in the game and
+/// profile lists. Setting the option to a boolean sets both.
+#[derive(Clone, Copy, PartialEq, Deserialize)]
+#[serde(from = "UserIncrementalSearch")]
+pub struct IncrementalSearch {
+    pub tree: bool,
+    pub lists: bool,
+}
+
+impl IncrementalSearch {
+    pub fn enabled_in(self, context: SearchContext) -> bool {
+        match context {
+            SearchContext::Normal => self.tree,
+            SearchContext::GameSelection
+            | SearchContext::GameCreation
+            | SearchContext::ProfileSelection => self.lists,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum UserIncrementalSearch {
+    All(bool),
+    PerContext {
+        tree: Option<bool>,
+        lists: Option<bool>,
+    },
+}
+
+impl From<UserIncrementalSearch> for IncrementalSearch {
+    fn from(user: UserIncrementalSearch) -> Self {
+        match user {
+            UserIncrementalSearch::All(enabled) => Self {
+                tree: enabled,
+                lists: enabled,
+            },
+            UserIncrementalSearch::PerContext { tree, lists } => Self {
+                tree: tree.unwrap_or(true),
+                lists: lists.unwrap_or(true),
+            },
+        }
+    }
+}
+
+/// Size of a popup window in rows and columns. It is limited by the size of the terminal.
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PopupSize {
+    pub height: u16,
+    pub width: u16,
+}
+
+impl PopupSize {
+    const MIN_HEIGHT: u16 = 10;
+    const MIN_WIDTH: u16 = 30;
+
+    /// Grows or shrinks the window by a step, staying within the terminal.
+    pub fn resize(&mut self, grow: bool) {
+        let (max_width, max_height) = crossterm::terminal::size().unwrap_or((u16::MAX, u16::MAX));
+
+        if grow {
+            self.height = self
+                .height
+                .saturating_add(2)
+                .min(max_height.max(Self::MIN_HEIGHT));
+            self.width = self
+                .width
+                .saturating_add(4)
+                .min(max_width.max(Self::MIN_WIDTH));
+        } else {
+            self.height = self
+                .height
+                .min(max_height)
+                .saturating_sub(2)
+                .max(Self::MIN_HEIGHT);
+            self.width = self
+                .width
+                .min(max_width)
+                .saturating_sub(4)
+                .max(Self::MIN_WIDTH);
+        }
+    }
+}
+
 #[derive(PartialEq)]
 pub struct Icons {
     pub folder_open: String,
@@ -89,8 +260,49 @@ where
 pub struct UserOptions {
     auto_mark_save_file: Option<bool>,
     hide_extensions: Option<bool>,
-    incremental_search: Option<bool>,
+    toggled_extension: Option<String>,
+    incremental_search: Option<IncrementalSearch>,
     rename: Option<RenameOptions>,
+    list_sort: Option<ListSort>,
+    wrap_lists: Option<bool>,
+    expand_on_directory_jump: Option<bool>,
+    start_view: Option<StartView>,
+    import_name_template: Option<String>,
+    import_overwrite: Option<bool>,
+    import_group_by_date: Option<bool>,
+    mark_import_active: Option<bool>,
+    broadcast_to_current: Option<bool>,
+    message_timeout: Option<u64>,
+    log: Option<bool>,
+    diff_command: Option<String>,
+    autosave_interval: Option<u64>,
+    bell_on_complete: Option<bool>,
+    backup_before_load: Option<bool>,
+    full_width_highlight: Option<bool>,
+    scrollbar: Option<bool>,
+    line_numbers: Option<LineNumbers>,
+    pinned_first: Option<bool>,
+    show_counts: Option<bool>,
+    active_marker: Option<String>,
+    identical_marker: Option<String>,
+    folders_first: Option<bool>,
+    show_root: Option<bool>,
+    compact_folders: Option<bool>,
+    path_display: Option<PathDisplay>,
+    warn_on_newer: Option<bool>,
+    confirm_move: Option<bool>,
+    delete_requires_double_press: Option<bool>,
+    confirm_deletion_by_name: Option<bool>,
+    alternate_screen: Option<bool>,
+    fuzzy_tiebreak: Option<FuzzyTiebreak>,
+    fuzzy_remember_query: Option<bool>,
+    fuzzy_empty_order: Option<FuzzyEmptyOrder>,
+    reveal_search_matches: Option<bool>,
+    after_delete: Option<AfterDelete>,
+    enter_action: Option<EnterAction>,
+    remember_popup_sizes: Option<bool>,
+    fuzzy_finder_size: Option<PopupSize>,
+    help_size: Option<PopupSize>,
     #[serde(default, deserialize_with = "deserialize_icons")]
     icons: Option<Icons>,
 }
@@ -98,8 +310,49 @@ pub struct UserOptions {
 pub struct Options {
     pub auto_mark_save_file: bool,
     pub hide_extensions: bool,
-    pub incremental_search: bool,
+    pub toggled_extension: String,
+    pub incremental_search: IncrementalSearch,
     pub rename: RenameOptions,
+    pub list_sort: ListSort,
+    pub wrap_lists: bool,
+    pub expand_on_directory_jump: bool,
+    pub start_view: StartView,
+    pub import_name_template: String,
+    pub import_overwrite: bool,
+    pub import_group_by_date: bool,
+    pub mark_import_active: bool,
+    pub broadcast_to_current: bool,
+    pub message_timeout: u64,
+    pub log: bool,
+    pub diff_command: String,
+    pub autosave_interval: u64,
+    pub bell_on_complete: bool,
+    pub backup_before_load: bool,
+    pub full_width_highlight: bool,
+    pub scrollbar: bool,
+    pub line_numbers: LineNumbers,
+    pub pinned_first: bool,
+    pub show_counts: bool,
+    pub active_marker: String,
+    pub identical_marker: String,
+    pub folders_first: bool,
+    pub show_root: bool,
+    pub compact_folders: bool,
+    pub path_display: PathDisplay,
+    pub warn_on_newer: bool,
+    pub confirm_move: bool,
+    pub delete_requires_double_press: bool,
+    pub confirm_deletion_by_name: bool,
+    pub alternate_screen: bool,
+    pub fuzzy_tiebreak: FuzzyTiebreak,
+    pub fuzzy_remember_query: bool,
+    pub fuzzy_empty_order: FuzzyEmptyOrder,
+    pub reveal_search_matches: bool,
+    pub after_delete: AfterDelete,
+    pub enter_action: EnterAction,
+    pub remember_popup_sizes: bool,
+    pub fuzzy_finder_size: PopupSize,
+    pub help_size: PopupSize,
     pub icons: Icons,
 }
 
@@ -108,8 +361,58 @@ impl Default for Options {
         Options {
             auto_mark_save_file: false,
             hide_extensions: false,
-            incremental_search: true,
+            toggled_extension: String::from("bak"),
+            incremental_search: IncrementalSearch {
+                tree: true,
+                lists: true,
+            },
             rename: RenameOptions::default(),
+            list_sort: ListSort::default(),
+            wrap_lists: true,
+            expand_on_directory_jump: false,
+            start_view: StartView::default(),
+            import_name_template: String::from("{name}"),
+            import_overwrite: false,
+            import_group_by_date: false,
+            mark_import_active: false,
+            broadcast_to_current: false,
+            message_timeout: 10,
+            log: false,
+            diff_command: String::from(if cfg!(windows) { "fc /b" } else { "cmp -l" }),
+            autosave_interval: 0,
+            bell_on_complete: false,
+            backup_before_load: false,
+            full_width_highlight: true,
+            scrollbar: false,
+            line_numbers: LineNumbers::default(),
+            pinned_first: false,
+            show_counts: false,
+            active_marker: String::from(" (*)"),
+            identical_marker: String::new(),
+            folders_first: false,
+            show_root: false,
+            compact_folders: false,
+            path_display: PathDisplay::Name,
+            warn_on_newer: false,
+            confirm_move: false,
+            delete_requires_double_press: false,
+            confirm_deletion_by_name: false,
+            alternate_screen: true,
+            fuzzy_tiebreak: FuzzyTiebreak::default(),
+            fuzzy_remember_query: false,
+            fuzzy_empty_order: FuzzyEmptyOrder::default(),
+            reveal_search_matches: false,
+            after_delete: AfterDelete::default(),
+            enter_action: EnterAction::default(),
+            remember_popup_sizes: false,
+            fuzzy_finder_size: PopupSize {
+                height: 50,
+                width: 90,
+            },
+            help_size: PopupSize {
+                height: 45,
+                width: 80,
+            },
             icons: Icons::default(),
         }
     }
@@ -129,8 +432,49 @@ impl MergeConfig for Options {
 
         set_options_field!(auto_mark_save_file);
         set_options_field!(hide_extensions);
+        set_options_field!(toggled_extension);
         set_options_field!(incremental_search);
         set_options_field!(rename);
+        set_options_field!(list_sort);
+        set_options_field!(wrap_lists);
+        set_options_field!(expand_on_directory_jump);
+        set_options_field!(start_view);
+        set_options_field!(import_name_template);
+        set_options_field!(import_overwrite);
+        set_options_field!(import_group_by_date);
+        set_options_field!(mark_import_active);
+        set_options_field!(broadcast_to_current);
+        set_options_field!(message_timeout);
+        set_options_field!(log);
+        set_options_field!(diff_command);
+        set_options_field!(autosave_interval);
+        set_options_field!(bell_on_complete);
+        set_options_field!(backup_before_load);
+        set_options_field!(full_width_highlight);
+        set_options_field!(scrollbar);
+        set_options_field!(line_numbers);
+        set_options_field!(pinned_first);
+        set_options_field!(show_counts);
+        set_options_field!(active_marker);
+        set_options_field!(identical_marker);
+        set_options_field!(folders_first);
+        set_options_field!(show_root);
+        set_options_field!(compact_folders);
+        set_options_field!(path_display);
+        set_options_field!(warn_on_newer);
+        set_options_field!(confirm_move);
+        set_options_field!(delete_requires_double_press);
+        set_options_field!(confirm_deletion_by_name);
+        set_options_field!(alternate_screen);
+        set_options_field!(fuzzy_tiebreak);
+        set_options_field!(fuzzy_remember_query);
+        set_options_field!(fuzzy_empty_order);
+        set_options_field!(reveal_search_matches);
+        set_options_field!(after_delete);
+        set_options_field!(enter_action);
+        set_options_field!(remember_popup_sizes);
+        set_options_field!(fuzzy_finder_size);
+        set_options_field!(help_size);
         set_options_field!(icons);
 
         Ok(())
@@ -150,14 +494,104 @@ mod tests {
         let UserOptions {
             auto_mark_save_file,
             hide_extensions,
+            toggled_extension,
             incremental_search,
             rename,
+            list_sort,
+            wrap_lists,
+            expand_on_directory_jump,
+            start_view,
+            import_name_template,
+            import_overwrite,
+            import_group_by_date,
+            mark_import_active,
+            broadcast_to_current,
+            message_timeout,
+            log,
+            diff_command,
+            autosave_interval,
+            bell_on_complete,
+            backup_before_load,
+            full_width_highlight,
+            scrollbar,
+            line_numbers,
+            pinned_first,
+            show_counts,
+            active_marker,
+            identical_marker,
+            folders_first,
+            show_root,
+            compact_folders,
+            path_display,
+            warn_on_newer,
+            confirm_move,
+            delete_requires_double_press,
+            confirm_deletion_by_name,
+            alternate_screen,
+            fuzzy_tiebreak,
+            fuzzy_remember_query,
+            fuzzy_empty_order,
+            reveal_search_matches,
+            after_delete,
+            enter_action,
+            remember_popup_sizes,
+            fuzzy_finder_size,
+            help_size,
             icons,
         } = user_config.options;
 
         assert!(auto_mark_save_file.is_some_and(|opt| opt == default.auto_mark_save_file));
         assert!(hide_extensions.is_some_and(|opt| opt == default.hide_extensions));
+        assert!(toggled_extension.is_some_and(|opt| opt == default.toggled_extension));
         assert!(incremental_search.is_some_and(|opt| opt == default.incremental_search));
+        assert!(list_sort.is_some_and(|opt| opt == default.list_sort));
+        assert!(wrap_lists.is_some_and(|opt| opt == default.wrap_lists));
+        assert!(
+            expand_on_directory_jump.is_some_and(|opt| opt == default.expand_on_directory_jump)
+        );
+        assert!(start_view.is_some_and(|opt| opt == default.start_view));
+        assert!(import_name_template.is_some_and(|opt| opt == default.import_name_template));
+        assert!(import_overwrite.is_some_and(|opt| opt == default.import_overwrite));
+        assert!(import_group_by_date.is_some_and(|opt| opt == default.import_group_by_date));
+        assert!(mark_import_active.is_some_and(|opt| opt == default.mark_import_active));
+        assert!(broadcast_to_current.is_some_and(|opt| opt == default.broadcast_to_current));
+        assert!(message_timeout.is_some_and(|opt| opt == default.message_timeout));
+        assert!(log.is_some_and(|opt| opt == default.log));
+        // the example uses the default of the unix-like systems
+        assert!(diff_command.is_some_and(|opt| cfg!(windows) || opt == default.diff_command));
+        assert!(autosave_interval.is_some_and(|opt| opt == default.autosave_interval));
+        assert!(bell_on_complete.is_some_and(|opt| opt == default.bell_on_complete));
+        assert!(backup_before_load.is_some_and(|opt| opt == default.backup_before_load));
+        assert!(full_width_highlight.is_some_and(|opt| opt == default.full_width_highlight));
+        assert!(scrollbar.is_some_and(|opt| opt == default.scrollbar));
+        assert!(line_numbers.is_some_and(|opt| opt == default.line_numbers));
+        assert!(pinned_first.is_some_and(|opt| opt == default.pinned_first));
+        assert!(show_counts.is_some_and(|opt| opt == default.show_counts));
+        assert!(active_marker.is_some_and(|opt| opt == default.active_marker));
+        assert!(identical_marker.is_some_and(|opt| opt == default.identical_marker));
+        assert!(folders_first.is_some_and(|opt| opt == default.folders_first));
+        assert!(show_root.is_some_and(|opt| opt == default.show_root));
+        assert!(compact_folders.is_some_and(|opt| opt == default.compact_folders));
+        assert!(path_display.is_some_and(|opt| opt == default.path_display));
+        assert!(warn_on_newer.is_some_and(|opt| opt == default.warn_on_newer));
+        assert!(confirm_move.is_some_and(|opt| opt == default.confirm_move));
+        assert!(
+            delete_requires_double_press
+                .is_some_and(|opt| opt == default.delete_requires_double_press)
+        );
+        assert!(
+            confirm_deletion_by_name.is_some_and(|opt| opt == default.confirm_deletion_by_name)
+        );
+        assert!(alternate_screen.is_some_and(|opt| opt == default.alternate_screen));
+        assert!(fuzzy_tiebreak.is_some_and(|opt| opt == default.fuzzy_tiebreak));
+        assert!(fuzzy_remember_query.is_some_and(|opt| opt == default.fuzzy_remember_query));
+        assert!(fuzzy_empty_order.is_some_and(|opt| opt == default.fuzzy_empty_order));
+        assert!(reveal_search_matches.is_some_and(|opt| opt == default.reveal_search_matches));
+        assert!(after_delete.is_some_and(|opt| opt == default.after_delete));
+        assert!(enter_action.is_some_and(|opt| opt == default.enter_action));
+        assert!(remember_popup_sizes.is_some_and(|opt| opt == default.remember_popup_sizes));
+        assert!(fuzzy_finder_size.is_some_and(|opt| opt == default.fuzzy_finder_size));
+        assert!(help_size.is_some_and(|opt| opt == default.help_size));
         assert!(icons.is_some_and(|opt| opt == default.icons));
 
         let RenameOptions { empty, cursor: _ } = rename.unwrap();
@@ -165,4 +599,19 @@ mod tests {
         // `empty` should be empty
         assert!(empty.is_none());
     }
+
+    #[test]
+    fn incremental_search() {
+        let parse = |s: &str| {
+            let search = toml::from_str::<UserOptions>(s).unwrap().incremental_search;
+            search.map(|search| (search.tree, search.lists))
+        };
+
+        assert_eq!(parse("incremental_search = false"), Some((false, false)));
+        assert_eq!(
+            parse("incremental_search = { tree = false }"),
+            Some((false, true))
+        );
+        assert_eq!(parse(""), None);
+    }
 }
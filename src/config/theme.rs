@@ -129,12 +129,17 @@ fn parse_modifiers(modifiers: &str) -> Result<Modifier> {
 pub struct UserTheme {
     title: Option<UserStyle>,
     selected: Option<UserStyle>,
+    selected_name: Option<UserStyle>,
+    selected_fold: Option<UserStyle>,
     marked: Option<UserStyle>,
     active: Option<UserStyle>,
+    identical: Option<UserStyle>,
+    pinned: Option<UserStyle>,
     fuzzy_selected: Option<UserStyle>,
     highlight: Option<UserStyle>,
     fuzzy_prompt: Option<UserStyle>,
     fuzzy_counter: Option<UserStyle>,
+    fuzzy_counter_no_match: Option<UserStyle>,
     confirmation_border: Option<UserStyle>,
     error: Option<UserStyle>,
     warning: Option<UserStyle>,
@@ -144,12 +149,17 @@ pub struct UserTheme {
 pub struct Theme {
     pub title: Style,
     pub selected: Style,
+    pub selected_name: Style,
+    pub selected_fold: Style,
     pub marked: Style,
     pub active: Style,
+    pub identical: Style,
+    pub pinned: Style,
     pub fuzzy_selected: Style,
     pub highlight: Style,
     pub fuzzy_prompt: Style,
     pub fuzzy_counter: Style,
+    pub fuzzy_counter_no_match: Style,
     pub confirmation_border: Style,
     pub error: Style,
     pub warning: Style,
@@ -165,16 +175,21 @@ impl Default for Theme {
             selected: Style::default()
                 .fg(Color::Magenta)
                 .add_modifier(Modifier::BOLD),
+            selected_name: Style::default(),
+            selected_fold: Style::default(),
             marked: Style::default()
                 .fg(Color::DarkGray)
                 .add_modifier(Modifier::CROSSED_OUT),
             active: Style::default()
                 .fg(Color::Yellow)
                 .add_modifier(Modifier::BOLD),
+            identical: Style::default().fg(Color::Green),
+            pinned: Style::default().fg(Color::LightBlue),
             fuzzy_selected: Style::default().fg(Color::Magenta),
             highlight: Style::default().fg(Color::Yellow),
             fuzzy_prompt: Style::default().fg(Color::Blue),
             fuzzy_counter: Style::default().fg(Color::DarkGray),
+            fuzzy_counter_no_match: Style::default().fg(Color::Red),
             confirmation_border: Style::default().fg(Color::Blue),
             error: Style::default().fg(Color::Red),
             warning: Style::default().fg(Color::Yellow),
@@ -183,6 +198,32 @@ impl Default for Theme {
     }
 }
 
+impl Theme {
+    /// A theme without any styling, used when `NO_COLOR` is set. The selected entry of the tree
+    /// is still reversed, as nothing else tells where the cursor is.
+    pub fn plain() -> Self {
+        Theme {
+            title: Style::default(),
+            selected: Style::default().add_modifier(Modifier::REVERSED),
+            selected_name: Style::default(),
+            selected_fold: Style::default(),
+            marked: Style::default(),
+            active: Style::default(),
+            identical: Style::default(),
+            pinned: Style::default(),
+            fuzzy_selected: Style::default(),
+            highlight: Style::default(),
+            fuzzy_prompt: Style::default(),
+            fuzzy_counter: Style::default(),
+            fuzzy_counter_no_match: Style::default(),
+            confirmation_border: Style::default(),
+            error: Style::default(),
+            warning: Style::default(),
+            help: Style::default(),
+        }
+    }
+}
+
 impl MergeConfig for Theme {
     type Other = UserTheme;
 
@@ -199,12 +240,17 @@ impl MergeConfig for Theme {
 
         set_theme_field!(title);
         set_theme_field!(selected);
+        set_theme_field!(selected_name);
+        set_theme_field!(selected_fold);
         set_theme_field!(marked);
         set_theme_field!(active);
+        set_theme_field!(identical);
+        set_theme_field!(pinned);
         set_theme_field!(fuzzy_selected);
         set_theme_field!(highlight);
         set_theme_field!(fuzzy_prompt);
         set_theme_field!(fuzzy_counter);
+        set_theme_field!(fuzzy_counter_no_match);
         set_theme_field!(confirmation_border);
         set_theme_field!(error);
         set_theme_field!(warning);
@@ -226,12 +272,17 @@ mod tests {
         let UserTheme {
             title,
             selected,
+            selected_name,
+            selected_fold,
             marked,
             active,
+            identical,
+            pinned,
             fuzzy_selected,
             highlight,
             fuzzy_prompt,
             fuzzy_counter,
+            fuzzy_counter_no_match,
             confirmation_border,
             error,
             warning,
@@ -240,12 +291,17 @@ mod tests {
 
         assert!(title.is_some());
         assert!(selected.is_some());
+        assert!(selected_name.is_some());
+        assert!(selected_fold.is_some());
         assert!(marked.is_some());
         assert!(active.is_some());
+        assert!(identical.is_some());
+        assert!(pinned.is_some());
         assert!(fuzzy_selected.is_some());
         assert!(highlight.is_some());
         assert!(fuzzy_prompt.is_some());
         assert!(fuzzy_counter.is_some());
+        assert!(fuzzy_counter_no_match.is_some());
         assert!(confirmation_border.is_some());
         assert!(error.is_some());
         assert!(warning.is_some());
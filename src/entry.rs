@@ -1,17 +1,30 @@
 use crate::{
     config::OPTIONS,
     tree::{NodeId, Tree},
+    utils,
 };
 use anyhow::Result;
 use std::{
+    cell::OnceCell,
     ffi::OsStr,
     fmt::Display,
+    fs,
     path::{Path, PathBuf},
+    time::SystemTime,
 };
 
+/// Metadata of an entry that is read once and kept until the watcher reports a change to it.
+#[derive(Clone, Copy)]
+pub struct Metadata {
+    pub len: u64,
+    pub created: Option<SystemTime>,
+    pub modified: Option<SystemTime>,
+}
+
 pub struct Entry {
     pub path: PathBuf,
     is_folder: bool,
+    metadata: OnceCell<Option<Metadata>>,
 }
 
 impl Entry {
@@ -19,6 +32,7 @@ impl Entry {
         Self {
             is_folder: path.is_dir(),
             path: path.to_owned(),
+            metadata: OnceCell::new(),
         }
     }
 
@@ -36,14 +50,33 @@ impl Entry {
                 path.exists().then_some((path, entry.entries.as_deref()))
             });
 
-            let from_read_dir = path.read_dir()?.flatten().filter_map(|dir_entry| {
-                let name = dir_entry.file_name();
-                (name != ".state" && entries.iter().all(|entry| *entry.name != name))
-                    .then_some((dir_entry.path(), None))
+            // entries that are not in the state yet are placed in human order
+            let mut from_read_dir = (path.read_dir()?.flatten())
+                .filter_map(|dir_entry| {
+                    let name = dir_entry.file_name();
+                    (name != ".state" && entries.iter().all(|entry| *entry.name != name))
+                        .then_some((dir_entry.path(), None))
+                })
+                .collect::<Vec<_>>();
+            from_read_dir.sort_by(|(a, _), (b, _)| {
+                utils::natural_cmp(
+                    &a.file_name().unwrap().to_string_lossy(),
+                    &b.file_name().unwrap().to_string_lossy(),
+                )
             });
 
-            for (path, entries) in from_entries.chain(from_read_dir) {
-                let child_id = Entry::new(&path).add_to_tree(entries.unwrap_or_default(), tree)?;
+            let mut children = from_entries
+                .chain(from_read_dir)
+                .map(|(path, entries)| {
+                    Entry::new(&path).add_to_tree(entries.unwrap_or_default(), tree)
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            if OPTIONS.get().folders_first {
+                children.sort_by_key(|child_id| tree[*child_id].is_file());
+            }
+
+            for child_id in children {
                 tree.append(id, child_id);
             }
         }
@@ -52,11 +85,14 @@ impl Entry {
     }
 
     pub fn delete(&self) -> Result<()> {
-        Ok(if self.is_folder {
+        let res = if self.is_folder {
             std::fs::remove_dir_all(&self.path)
         } else {
             std::fs::remove_file(&self.path)
-        }?)
+        };
+
+        utils::log("delete", &[&self.path], &res);
+        Ok(res?)
     }
 
     pub fn name(&self) -> &OsStr {
@@ -70,11 +106,30 @@ impl Entry {
     pub fn is_file(&self) -> bool {
         !self.is_folder
     }
+
+    /// Returns the cached metadata, reading it on the first call. `None` if it can't be read.
+    pub fn metadata(&self) -> Option<Metadata> {
+        *self.metadata.get_or_init(|| {
+            fs::metadata(&self.path).ok().map(|metadata| {
+                let (created, modified) = utils::file_times(&metadata);
+
+                Metadata {
+                    len: metadata.len(),
+                    created,
+                    modified,
+                }
+            })
+        })
+    }
+
+    pub fn invalidate_metadata(&mut self) {
+        self.metadata.take();
+    }
 }
 
 impl Display for Entry {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let name = if OPTIONS.hide_extensions && self.is_file() {
+        let name = if OPTIONS.get().hide_extensions && self.is_file() {
             self.path.file_stem()
         } else {
             self.path.file_name()
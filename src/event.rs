@@ -1,8 +1,21 @@
+use crate::tree::NodeId;
 use crossterm::event::Event as CrosstermEvent;
 use notify::Event as NotifyEvent;
+use std::collections::HashSet;
 
 pub enum Event {
     Crossterm(CrosstermEvent),
     FileSystem(NotifyEvent),
     ClearMessage,
+    Progress {
+        done: usize,
+        total: usize,
+    },
+    TaskFinished(anyhow::Result<()>),
+    /// Saves identical to the active save found by the lookup numbered `lookup`.
+    IdenticalSaves {
+        lookup: u64,
+        ids: HashSet<NodeId>,
+    },
+    Autosave,
 }
@@ -0,0 +1,66 @@
+use crate::{
+    ui::Scroller,
+    utils,
+    watcher::{Context, FileSystemEvent, Kind},
+};
+use anyhow::Result;
+use std::collections::VecDeque;
+
+/// Number of events kept in the log. The oldest ones are dropped first.
+const CAPACITY: usize = 200;
+
+pub struct Record {
+    pub text: String,
+    pub failed: bool,
+}
+
+/// Log of the file system events handled by the app, shown in a popup.
+#[derive(Default)]
+pub struct EventLog {
+    records: VecDeque<Record>,
+    pub visible: bool,
+    pub scroller: Scroller,
+}
+
+impl EventLog {
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+    }
+
+    /// Records the event with the time it was handled at. Writes aren't recorded as they are
+    /// too frequent.
+    pub fn push(&mut self, event: &FileSystemEvent, res: &Result<()>) {
+        let context = match event.context {
+            Context::Game => "game",
+            Context::Profile => "profile",
+            Context::Entry => "entry",
+        };
+
+        let path = event.path.display();
+        let change = match &event.kind {
+            Kind::Create => format!("created {path}"),
+            Kind::Rename(new_path) => format!("renamed {path} -> {}", new_path.display()),
+            Kind::Delete => format!("deleted {path}"),
+            Kind::Modify => return,
+        };
+
+        let mut text = format!("{} {context} {change}", utils::current_time());
+        if let Err(e) = res {
+            text.push_str(&format!(": {e}"));
+        }
+
+        if self.records.len() == CAPACITY {
+            self.records.pop_front();
+        }
+
+        self.records.push_back(Record {
+            text,
+            failed: res.is_err(),
+        });
+    }
+
+    /// Returns the records, the most recent one first.
+    pub fn records(&self) -> impl Iterator<Item = &Record> {
+        self.records.iter().rev()
+    }
+}
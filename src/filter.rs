@@ -0,0 +1,95 @@
+use crate::tree::{NodeId, Tree};
+use nucleo_matcher::{
+    Matcher, Utf32String,
+    pattern::{AtomKind, CaseMatching, Normalization, Pattern},
+};
+use std::{collections::HashSet, fmt::Display};
+
+/// Hides the entries of the tree that don't match the pattern.
+#[derive(Default)]
+pub struct Filter {
+    matcher: Matcher,
+    pub pattern: String,
+    /// The matching nodes and their ancestors, or `None` if the filter is not active.
+    pub shown: Option<HashSet<NodeId>>,
+}
+
+impl Filter {
+    pub fn is_active(&self) -> bool {
+        self.shown.is_some()
+    }
+
+    /// Returns true if the pattern isn't empty and nothing matches it.
+    pub fn no_match(&self) -> bool {
+        self.shown.as_ref().is_some_and(HashSet::is_empty)
+    }
+
+    /// Matches the pattern against the nodes of the tree again. The filter is deactivated if the
+    /// pattern is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bawa::filter::Filter;
+    /// # use bawa::tree::Tree;
+    /// let mut tree = Tree::default();
+    /// let r = tree.add_value("r");
+    /// let a = tree.add_value("boss");
+    /// let b = tree.add_value("other");
+    /// let a_c = tree.add_value("before");
+    /// let a_d = tree.add_value("after");
+    /// tree.append(r, a);
+    /// tree.append(r, b);
+    /// tree.append(a, a_c);
+    /// tree.append(a, a_d);
+    ///
+    /// let mut filter = Filter::default();
+    /// filter.pattern = String::from("aft");
+    /// filter.update(&tree);
+    ///
+    /// let shown = filter.shown.as_ref().unwrap();
+    /// assert!(shown.contains(&a) && shown.contains(&a_d));
+    /// assert!(!shown.contains(&a_c) && !shown.contains(&b));
+    ///
+    /// filter.pattern.clear();
+    /// filter.update(&tree);
+    /// assert!(!filter.is_active());
+    /// ```
+    pub fn update<T: Display>(&mut self, tree: &Tree<T>) {
+        if self.pattern.is_empty() || tree.root().is_none() {
+            self.shown = None;
+            return;
+        }
+
+        let mut shown = HashSet::new();
+
+        for id in self.find_matches(tree) {
+            shown.insert(id);
+            shown.extend(tree.ancestors(id).take_while(|id| *id != NodeId::root()));
+        }
+
+        self.shown = Some(shown);
+    }
+
+    /// Returns the nodes whose names match the pattern, without their ancestors, in tree order.
+    pub fn find_matches<T: Display>(&mut self, tree: &Tree<T>) -> Vec<NodeId> {
+        let pattern = Pattern::new(
+            &self.pattern,
+            CaseMatching::Smart,
+            Normalization::Smart,
+            AtomKind::Substring,
+        );
+
+        (tree.descendants(NodeId::root()).skip(1))
+            .filter(|id| {
+                let name = Utf32String::from(tree[*id].to_string());
+                pattern.score(name.slice(..), &mut self.matcher).is_some()
+            })
+            .collect()
+    }
+
+    pub fn clear(&mut self) {
+        self.pattern.clear();
+        self.shown = None;
+    }
+}
@@ -1,7 +1,12 @@
 use crate::{
     app::StatefulList,
+    config::{
+        OPTIONS,
+        options::{FuzzyEmptyOrder, FuzzyTiebreak, PopupSize},
+    },
+    game::state::PopupSizes,
     input::{Input, handle_key_fuzzy_mode},
-    ui,
+    ui, utils,
 };
 use anyhow::Result;
 use crossterm::event::{Event, KeyCode};
@@ -11,10 +16,14 @@ use nucleo_matcher::{
     pattern::{CaseMatching, Normalization, Pattern},
 };
 use picker::Picker;
+use std::cmp::Ordering;
 
 mod item;
 pub mod picker;
 
+/// Maximum height of the fuzzy finder when it runs inline.
+const INLINE_HEIGHT: u16 = 25;
+
 pub struct FuzzyFinder {
     matcher: Matcher,
     pub input: Input,
@@ -23,6 +32,10 @@ pub struct FuzzyFinder {
     pub matched: StatefulList<Matched>,
     pub total_count: usize,
     pub match_count: usize,
+    /// Query of the previous search, restored with the `fuzzy_remember_query` option.
+    last_query: String,
+    /// Size set with alt-up and alt-down, the `fuzzy_finder_size` option otherwise.
+    pub size: Option<PopupSize>,
 }
 
 impl Default for FuzzyFinder {
@@ -35,11 +48,27 @@ impl Default for FuzzyFinder {
             matched: StatefulList::with_items(Vec::new()),
             total_count: 0,
             match_count: 0,
+            last_query: String::new(),
+            size: None,
         }
     }
 }
 
 impl FuzzyFinder {
+    pub fn size(&self) -> PopupSize {
+        self.size.unwrap_or(OPTIONS.get().fuzzy_finder_size)
+    }
+
+    pub fn resize(&mut self, grow: bool) {
+        self.size
+            .get_or_insert(OPTIONS.get().fuzzy_finder_size)
+            .resize(grow);
+
+        if OPTIONS.get().remember_popup_sizes {
+            let _ = PopupSizes::update(|sizes| sizes.fuzzy_finder = self.size);
+        }
+    }
+
     pub fn set_picker(&mut self, picker: impl Picker + 'static) {
         self.total_count = picker.items().len();
         self.picker = Some(Box::new(picker));
@@ -54,8 +83,19 @@ impl FuzzyFinder {
     }
 
     pub fn reset(&mut self) {
+        if !self.input.text.is_empty() {
+            self.last_query.clone_from(&self.input.text);
+        }
+
         self.input.set_text("");
         self.picker.take();
+        self.input.selected = false;
+    }
+
+    /// Restores the query of the previous search with the whole text selected.
+    pub fn restore_last_query(&mut self) {
+        self.input.set_text(&self.last_query);
+        self.input.selected = !self.last_query.is_empty();
     }
 
     pub fn update_matches(&mut self) {
@@ -85,7 +125,29 @@ impl FuzzyFinder {
         }
 
         self.match_count = self.matched.items.len();
-        self.matched.items.sort_by(|a, b| b.score.cmp(&a.score));
+
+        let picker = self.picker.as_ref().unwrap();
+        let order = if self.input.text.is_empty() {
+            OPTIONS.get().fuzzy_empty_order
+        } else {
+            FuzzyEmptyOrder::Score
+        };
+
+        self.matched.items.sort_by(|a, b| match order {
+            FuzzyEmptyOrder::Score => {
+                let tiebreak = match OPTIONS.get().fuzzy_tiebreak {
+                    FuzzyTiebreak::Length => Ordering::Equal,
+                    FuzzyTiebreak::Recency => picker.modified(b.idx).cmp(&picker.modified(a.idx)),
+                };
+
+                (b.score.cmp(&a.score))
+                    .then(tiebreak)
+                    .then_with(|| a.text.len().cmp(&b.text.len()))
+            }
+            FuzzyEmptyOrder::Recent => (picker.modified(b.idx).cmp(&picker.modified(a.idx)))
+                .then_with(|| utils::natural_cmp(&a.text, &b.text)),
+            FuzzyEmptyOrder::Path => utils::natural_cmp(&a.text, &b.text),
+        });
         self.matched.select_first();
     }
 
@@ -101,13 +163,15 @@ impl FuzzyFinder {
         self.update_matches();
 
         if self.matched.items.len() > 1 {
-            let mut terminal = ui::init_inline(25);
+            let height = crossterm::terminal::size()
+                .map_or(INLINE_HEIGHT, |(_, rows)| rows.min(INLINE_HEIGHT));
+            let mut terminal = ui::init_inline(height);
 
             loop {
                 terminal.draw(|f| ui::draw_fuzzy_finder(f, self, f.area()))?;
 
-                if let Event::Key(key) = crossterm::event::read()? {
-                    match key.code {
+                match crossterm::event::read()? {
+                    Event::Key(key) => match key.code {
                         KeyCode::Enter => {
                             break;
                         }
@@ -116,7 +180,10 @@ impl FuzzyFinder {
                             break;
                         }
                         _ => handle_key_fuzzy_mode(key, self),
-                    }
+                    },
+                    // the viewport is clamped to the new size of the terminal
+                    Event::Resize(..) => terminal.autoresize()?,
+                    _ => (),
                 }
             }
 
@@ -1,9 +1,33 @@
-use crate::{app::App, tree::NodeId};
+use crate::{
+    app::App,
+    config::{
+        OPTIONS,
+        options::{FuzzyEmptyOrder, FuzzyTiebreak},
+    },
+    entry::Entry,
+    tree::NodeId,
+};
 use anyhow::Result;
 use nucleo_matcher::Utf32String;
+use std::time::SystemTime;
+
+/// Returns the modification times of the entries if they are needed to order the matches, or an
+/// empty vector otherwise.
+fn modified_times<'a>(entries: impl Iterator<Item = &'a Entry>) -> Vec<Option<SystemTime>> {
+    if OPTIONS.get().fuzzy_tiebreak != FuzzyTiebreak::Recency
+        && OPTIONS.get().fuzzy_empty_order != FuzzyEmptyOrder::Recent
+    {
+        return Vec::new();
+    }
+
+    entries
+        .map(|entry| entry.metadata().and_then(|metadata| metadata.modified))
+        .collect()
+}
 
 pub struct Local {
     entries: Vec<(Utf32String, NodeId)>,
+    modified: Vec<Option<SystemTime>>,
 }
 
 impl Local {
@@ -15,9 +39,30 @@ impl Local {
             .iter_ids()
             .filter(|id| tree[*id].is_file())
             .map(|id| (Utf32String::from(profile.rel_path_to(&tree[id].path)), id))
-            .collect();
+            .collect::<Vec<_>>();
+        let modified = modified_times(entries.iter().map(|(_, id)| &tree[*id].value));
 
-        Self { entries }
+        Self { entries, modified }
+    }
+
+    /// Creates a picker over the pinned entries of the selected profile.
+    pub fn pinned(app: &App) -> Self {
+        let profile = app.games.get_profile().unwrap();
+        let tree = &profile.entries;
+
+        let entries = tree
+            .iter_ids()
+            .skip(1)
+            .filter(|id| profile.is_pinned(&tree[*id].path))
+            .map(|id| (Utf32String::from(profile.rel_path_to(&tree[id].path)), id))
+            .collect::<Vec<_>>();
+        let modified = modified_times(entries.iter().map(|(_, id)| &tree[*id].value));
+
+        Self { entries, modified }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
     }
 }
 
@@ -32,21 +77,98 @@ impl Picker for Local {
             app.games.get_entries_mut().unwrap(),
         );
     }
+
+    fn modified(&self, idx: usize) -> Option<SystemTime> {
+        self.modified.get(idx).copied().flatten()
+    }
+}
+
+/// Picker over the lines of the text files in the selected profile. Binary files and files larger
+/// than `MAX_FILE_SIZE` are skipped.
+pub struct Content {
+    lines: Vec<(Utf32String, NodeId)>,
+}
+
+impl Content {
+    const MAX_FILE_SIZE: u64 = 1024 * 1024;
+    const MAX_LINES: usize = 100_000;
+    const MAX_LINE_WIDTH: usize = 200;
+
+    pub fn new(app: &App) -> Self {
+        let profile = app.games.get_profile().unwrap();
+        let tree = &profile.entries;
+        let mut lines = Vec::new();
+
+        for id in tree.iter_ids().filter(|id| tree[*id].is_file()) {
+            let path = &tree[id].path;
+
+            let Some(content) = std::fs::metadata(path)
+                .ok()
+                .filter(|metadata| metadata.len() <= Self::MAX_FILE_SIZE)
+                .and_then(|_| std::fs::read_to_string(path).ok())
+            else {
+                continue;
+            };
+
+            let rel_path = profile.rel_path_to(path);
+
+            for (idx, line) in content.lines().enumerate() {
+                let line = line.trim();
+
+                if line.is_empty() {
+                    continue;
+                }
+
+                let line = line.chars().take(Self::MAX_LINE_WIDTH).collect::<String>();
+                let formatted = format!("{rel_path}:{}: {line}", idx + 1);
+                lines.push((Utf32String::from(formatted), id));
+
+                if lines.len() == Self::MAX_LINES {
+                    return Self { lines };
+                }
+            }
+        }
+
+        Self { lines }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+}
+
+impl Picker for Content {
+    fn items(&self) -> Vec<Utf32String> {
+        self.lines.iter().map(|(s, _)| s.clone()).collect()
+    }
+
+    fn jump(&self, idx: usize, app: &mut App) {
+        app.tree_state.select(
+            Some(self.lines[idx].1),
+            app.games.get_entries_mut().unwrap(),
+        );
+    }
 }
 
 pub struct Global {
     entries: Vec<(Utf32String, (usize, NodeId))>,
+    modified: Vec<Option<SystemTime>>,
 }
 
 impl Global {
     pub fn new(app: &mut App) -> Result<Self> {
         let mut entries = Vec::new();
+        let mut modified = Vec::new();
 
         for (idx, profile) in app.games.get_profiles_mut().items.iter_mut().enumerate() {
             profile.load_entries()?;
             let tree = &profile.entries;
+            let files = (tree.iter_ids())
+                .filter(|id| tree[*id].is_file())
+                .collect::<Vec<_>>();
 
-            entries.extend(tree.iter_ids().filter(|id| tree[*id].is_file()).map(|id| {
+            modified.extend(modified_times(files.iter().map(|id| &tree[*id].value)));
+            entries.extend(files.into_iter().map(|id| {
                 const MAX_NAME_WIDTH: usize = 20;
                 let path = profile.rel_path_to(&tree[id].path);
                 let mut name = profile.name().into_owned();
@@ -64,7 +186,7 @@ impl Global {
             }));
         }
 
-        Ok(Self { entries })
+        Ok(Self { entries, modified })
     }
 }
 
@@ -80,9 +202,69 @@ impl Picker for Global {
         app.tree_state
             .select(Some(node_id), app.games.get_entries_mut().unwrap());
     }
+
+    fn modified(&self, idx: usize) -> Option<SystemTime> {
+        self.modified.get(idx).copied().flatten()
+    }
+}
+
+/// Picker over the games, selecting the picked game in the game selection window.
+pub struct GameList {
+    names: Vec<Utf32String>,
+}
+
+impl GameList {
+    pub fn new(app: &App) -> Self {
+        let names = (app.games.inner.items.iter())
+            .map(|game| Utf32String::from(game.name().into_owned()))
+            .collect();
+
+        Self { names }
+    }
+}
+
+impl Picker for GameList {
+    fn items(&self) -> Vec<Utf32String> {
+        self.names.clone()
+    }
+
+    fn jump(&self, idx: usize, app: &mut App) {
+        app.games.inner.state.select(Some(idx));
+    }
+}
+
+/// Picker over the profiles of the selected game, selecting the picked profile in the profile
+/// selection window.
+pub struct ProfileList {
+    names: Vec<Utf32String>,
+}
+
+impl ProfileList {
+    pub fn new(app: &App) -> Self {
+        let names = (app.games.get_profiles().items.iter())
+            .map(|profile| Utf32String::from(profile.name().into_owned()))
+            .collect();
+
+        Self { names }
+    }
+}
+
+impl Picker for ProfileList {
+    fn items(&self) -> Vec<Utf32String> {
+        self.names.clone()
+    }
+
+    fn jump(&self, idx: usize, app: &mut App) {
+        app.games.get_profiles_mut().state.select(Some(idx));
+    }
 }
 
 pub trait Picker {
     fn items(&self) -> Vec<Utf32String>;
     fn jump(&self, idx: usize, app: &mut App);
+
+    /// Returns the modification time of the item, used for breaking ties between the matches.
+    fn modified(&self, _idx: usize) -> Option<SystemTime> {
+        None
+    }
 }
@@ -7,9 +7,19 @@ pub struct Preset {
     steam_app_id: &'static str,
     folder_name: &'static str,
     file_name: &'static str,
+    in_documents: bool,
 }
 
 impl Preset {
+    /// Returns the paths of the save files found for the preset.
+    pub fn get_savefile_locations(&self) -> Result<Vec<String>> {
+        if self.in_documents {
+            self.get_from_documents_dir()
+        } else {
+            self.get_from_data_dir()
+        }
+    }
+
     pub fn get_from_data_dir(&self) -> Result<Vec<String>> {
         let main_directory = match dirs::data_dir() {
             Some(mut path) => {
@@ -85,6 +95,7 @@ const DARK_SOULS_REMASTERED: Preset = Preset {
     #[cfg(windows)]
     folder_name: "NBGI\\DARK SOULS REMASTERED",
     file_name: "DRAKS0005.sl2",
+    in_documents: true,
 };
 
 const DARK_SOULS2: Preset = Preset {
@@ -92,6 +103,7 @@ const DARK_SOULS2: Preset = Preset {
     steam_app_id: "236430",
     folder_name: "DarkSoulsII",
     file_name: "DARKSII0000.sl2",
+    in_documents: false,
 };
 
 const DARK_SOULS2_SOTFS: Preset = Preset {
@@ -99,6 +111,7 @@ const DARK_SOULS2_SOTFS: Preset = Preset {
     steam_app_id: "335300",
     folder_name: "DarkSoulsII",
     file_name: "DS2SOFS0000.sl2",
+    in_documents: false,
 };
 
 const DARK_SOULS3: Preset = Preset {
@@ -106,6 +119,7 @@ const DARK_SOULS3: Preset = Preset {
     steam_app_id: "374320",
     folder_name: "DarkSoulsIII",
     file_name: "DS30000.sl2",
+    in_documents: false,
 };
 
 const SEKIRO: Preset = Preset {
@@ -113,6 +127,7 @@ const SEKIRO: Preset = Preset {
     steam_app_id: "814380",
     folder_name: "Sekiro",
     file_name: "S0000.sl2",
+    in_documents: false,
 };
 
 const ELDEN_RING: Preset = Preset {
@@ -120,8 +135,20 @@ const ELDEN_RING: Preset = Preset {
     steam_app_id: "1245620",
     folder_name: "EldenRing",
     file_name: "ER0000.sl2",
+    in_documents: false,
 };
 
+pub fn presets() -> Vec<Preset> {
+    vec![
+        DARK_SOULS_REMASTERED,
+        DARK_SOULS2,
+        DARK_SOULS2_SOTFS,
+        DARK_SOULS3,
+        SEKIRO,
+        ELDEN_RING,
+    ]
+}
+
 pub fn is_64_bit_steam_id(dir_name: &str) -> bool {
     dir_name.starts_with("76561")
         && dir_name.len() == 17
@@ -170,15 +197,6 @@ impl CreatingGame {
     }
 
     pub fn load_presets(&mut self) {
-        let presets = vec![
-            DARK_SOULS_REMASTERED,
-            DARK_SOULS2,
-            DARK_SOULS2_SOTFS,
-            DARK_SOULS3,
-            SEKIRO,
-            ELDEN_RING,
-        ];
-
-        self.step = Step::Presets(StatefulList::with_items(presets));
+        self.step = Step::Presets(StatefulList::with_items(presets()));
     }
 }
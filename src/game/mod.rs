@@ -1,13 +1,16 @@
 use crate::{
     app::StatefulList,
+    config::{OPTIONS, options::ListSort},
     entry::Entry,
     tree::{NodeId, Tree},
     utils,
     watcher::HandleFileSystemEvent,
 };
-use anyhow::Result;
+use anyhow::{Context, Result, ensure};
 use profile::Profile;
+use state::{LastUsed, Notes};
 use std::{
+    collections::HashSet,
     fmt::Display,
     fs::{self, File},
     mem,
@@ -20,12 +23,64 @@ pub mod profile;
 pub mod state;
 
 pub fn read_games() -> Result<Vec<Game>> {
-    Ok(utils::get_state_dir()?
+    let last_used = LastUsed::read();
+
+    let mut games = utils::get_state_dir()?
         .read_dir()?
         .flatten()
         .filter(|dir_entry| dir_entry.file_type().unwrap().is_dir())
-        .map(|dir_entry| Game::new(dir_entry.path()))
-        .collect())
+        .map(|dir_entry| {
+            let mut game = Game::new(dir_entry.path());
+            game.last_used = last_used.get(&game.path);
+            game
+        })
+        .collect::<Vec<Game>>();
+
+    sort_list(&mut games);
+
+    Ok(games)
+}
+
+/// Common interface of the items listed in the game and profile selection windows.
+pub trait SelectionItem {
+    fn name(&self) -> std::borrow::Cow<'_, str>;
+    fn last_used(&self) -> Option<u64>;
+
+    fn note(&self) -> Option<&str> {
+        None
+    }
+}
+
+/// Sorts games or profiles according to the `list_sort` option.
+fn sort_list<T: SelectionItem>(items: &mut [T]) {
+    items.sort_by(|a, b| utils::natural_cmp(&a.name(), &b.name()));
+
+    if OPTIONS.get().list_sort == ListSort::LastUsed {
+        items.sort_by_key(|item| std::cmp::Reverse(item.last_used()));
+    }
+}
+
+/// Checks that every savefile path has a file name and that no two of them share one, as the
+/// save files are kept by their file names in the folders of games with multiple save files.
+fn validate_savefile_paths(savefile_paths: &[PathBuf]) -> Result<()> {
+    let mut names = HashSet::new();
+
+    for savefile_path in savefile_paths {
+        let name = savefile_path.file_name().with_context(|| {
+            format!(
+                "Savefile path {} doesn't have a file name.",
+                savefile_path.display()
+            )
+        })?;
+
+        ensure!(
+            names.insert(name),
+            "Savefile paths can't share the file name {}.",
+            name.to_string_lossy()
+        );
+    }
+
+    Ok(())
 }
 
 pub fn get_active_game_file() -> Result<PathBuf> {
@@ -45,9 +100,12 @@ pub fn get_active_game() -> Result<String> {
 
 pub struct Game {
     pub path: PathBuf,
-    pub savefile_path: Option<PathBuf>,
+    /// Paths of the save files of the game. Games with multiple save files keep each save in a
+    /// folder that contains a file with the name of each save file.
+    pub savefile_paths: Vec<PathBuf>,
     pub profiles: StatefulList<Profile>,
     pub active_profile: Option<usize>,
+    pub last_used: Option<u64>,
     pub pending_create: bool,
 }
 
@@ -55,9 +113,10 @@ impl Game {
     pub fn new(path: PathBuf) -> Self {
         Self {
             path,
-            savefile_path: None,
+            savefile_paths: Vec::new(),
             profiles: StatefulList::with_items(Vec::new()),
             active_profile: None,
+            last_used: None,
             pending_create: false,
         }
     }
@@ -67,25 +126,47 @@ impl Game {
     }
 
     pub fn read_profiles(&self) -> Result<Vec<Profile>> {
-        Ok(self
+        let last_used = LastUsed::read();
+        let notes = Notes::read();
+
+        let mut profiles = self
             .path
             .read_dir()?
             .flatten()
             .filter(|dir_entry| dir_entry.file_type().unwrap().is_dir())
-            .map(|dir_entry| Profile::new(dir_entry.path()))
-            .collect())
+            .map(|dir_entry| {
+                let mut profile = Profile::new(dir_entry.path());
+                profile.last_used = last_used.get(&profile.path);
+                profile.note = notes.get(&profile.path);
+                profile
+            })
+            .collect::<Vec<Profile>>();
+
+        sort_list(&mut profiles);
+
+        Ok(profiles)
+    }
+
+    fn read_state(&self) -> Option<state::GameState> {
+        fs::read(self.path.join(".state"))
+            .ok()
+            .and_then(|s| state::GameState::from_bytes(&s))
+    }
+
+    /// Reads the savefile paths from the state file without loading the profiles.
+    pub fn read_savefile_paths(&self) -> Vec<PathBuf> {
+        self.read_state()
+            .map(state::GameState::savefile_paths)
+            .unwrap_or_default()
     }
 
     fn load_profiles(&mut self) -> Result<()> {
         let mut profiles = self.read_profiles()?;
-        let state_file = self.path.join(".state");
 
-        if let Some(state) = fs::read(state_file)
-            .ok()
-            .and_then(|s| bincode::deserialize::<state::GameState>(&s).ok())
-        {
-            self.savefile_path = state.savefile_path.map(PathBuf::from);
-            if let Some(name) = state.active_profile {
+        if let Some(state) = self.read_state() {
+            let active_profile = state.active_profile.clone();
+            self.savefile_paths = state.savefile_paths();
+            if let Some(name) = active_profile {
                 self.active_profile = profiles.iter().position(|profile| profile.name() == name);
 
                 if let Some(idx) = self.active_profile {
@@ -131,9 +212,18 @@ impl Game {
         Ok(())
     }
 
+    pub fn set_selected_profile_note(&mut self, note: &str) -> Result<()> {
+        match self.profiles.get_selected_mut() {
+            Some(profile) => profile.set_note(note),
+            None => Ok(()),
+        }
+    }
+
     pub fn delete_selected_profile(&self) -> Result<()> {
         if let Some(profile) = self.profiles.get_selected() {
-            std::fs::remove_dir_all(&profile.path)?;
+            let res = std::fs::remove_dir_all(&profile.path);
+            utils::log("delete profile", &[&profile.path], &res);
+            res?;
         }
 
         Ok(())
@@ -154,16 +244,92 @@ impl Game {
 
         let profile = self.profiles.get_selected_mut().unwrap();
         profile.load_entries()?;
+        // the timestamp is only shown in the selection window, so failing to record it doesn't
+        // stop the profile from being selected
+        let res = LastUsed::record(&profile.path);
+        utils::log("record last use", &[&profile.path], &res);
+        if let Ok(time) = res {
+            profile.last_used = Some(time);
+        }
         self.update_active_profile(self.profiles.state.selected())?;
 
         Ok(true)
     }
 
-    pub fn set_savefile_path(&mut self, savefile_path: &str) -> Result<()> {
-        self.savefile_path = Some(PathBuf::from(savefile_path));
+    pub fn set_savefile_paths(&mut self, savefile_paths: Vec<PathBuf>) -> Result<()> {
+        validate_savefile_paths(&savefile_paths)?;
+        self.savefile_paths = savefile_paths;
         self.write_state()
     }
 
+    /// Returns the path whose name and extension are given to the saves imported from the live
+    /// save, or `None` if no savefile path is set.
+    pub fn import_name_source(&self) -> Option<PathBuf> {
+        match self.savefile_paths.as_slice() {
+            [] => None,
+            [savefile_path] => Some(savefile_path.clone()),
+            // the save files are imported into a folder, which doesn't have an extension
+            [savefile_path, ..] => {
+                Some(PathBuf::from(savefile_path.file_stem().unwrap_or_default()))
+            }
+        }
+    }
+
+    /// Returns the files of the save at `path` that correspond to the savefile paths of the
+    /// game, which is `path` itself if the game has a single save file.
+    pub fn save_files_in(&self, path: &Path) -> Vec<PathBuf> {
+        match self.savefile_paths.as_slice() {
+            [_] => vec![path.to_owned()],
+            // a path without a file name gives the folder itself, which can't be copied to or
+            // from, rather than shifting the files paired with the rest of the paths
+            savefile_paths => (savefile_paths.iter())
+                .map(|savefile_path| path.join(savefile_path.file_name().unwrap_or_default()))
+                .collect(),
+        }
+    }
+
+    /// Returns true if `path` can be loaded: a file if the game has a single save file, or a
+    /// folder that contains all of the save files otherwise.
+    pub fn is_save(&self, path: &Path) -> bool {
+        match self.savefile_paths.as_slice() {
+            [] => false,
+            [_] => path.is_file(),
+            _ => path.is_dir() && self.save_files_in(path).iter().all(|file| file.is_file()),
+        }
+    }
+
+    /// Copies the save files of the game to the save at `path`.
+    pub fn copy_savefiles_to(&self, path: &Path, operation: &str) -> Result<()> {
+        if self.savefile_paths.len() > 1 {
+            fs::create_dir_all(path)?;
+        }
+
+        for (savefile_path, file) in self.savefile_paths.iter().zip(self.save_files_in(path)) {
+            let res = fs::copy(savefile_path, &file);
+            utils::log(operation, &[savefile_path, &file], &res);
+            res?;
+        }
+
+        Ok(())
+    }
+
+    /// Copies the save at `path` to the savefile paths of the game.
+    pub fn copy_savefiles_from(&self, path: &Path, operation: &str) -> Result<()> {
+        ensure!(
+            self.is_save(path),
+            "The folder doesn't contain the {} save files of the game.",
+            self.savefile_paths.len()
+        );
+
+        for (savefile_path, file) in self.savefile_paths.iter().zip(self.save_files_in(path)) {
+            let res = fs::copy(&file, savefile_path);
+            utils::log(operation, &[&file, savefile_path], &res);
+            res?;
+        }
+
+        Ok(())
+    }
+
     pub fn update_active_profile(&mut self, profile_idx: Option<usize>) -> Result<()> {
         self.active_profile = profile_idx;
         self.write_state()
@@ -186,6 +352,16 @@ impl Game {
     }
 }
 
+impl SelectionItem for Game {
+    fn name(&self) -> std::borrow::Cow<'_, str> {
+        self.name()
+    }
+
+    fn last_used(&self) -> Option<u64> {
+        self.last_used
+    }
+}
+
 impl Display for Game {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.name())
@@ -212,6 +388,8 @@ impl HandleFileSystemEvent for Game {
 
         let profile = &mut profiles[idx];
         new_path.clone_into(&mut profile.path);
+        LastUsed::rename(path, new_path)?;
+        Notes::rename(path, new_path)?;
 
         if matches!(self.active_profile, Some(active_idx) if active_idx == idx) {
             profile.entries.update_paths(NodeId::root(), new_path)?;
@@ -230,6 +408,8 @@ impl HandleFileSystemEvent for Game {
 
         if let Some(idx) = profiles.iter().position(|profile| profile.path == path) {
             self.profiles.items.remove(idx);
+            LastUsed::remove(path)?;
+            Notes::remove(path)?;
 
             if matches!(self.active_profile, Some(active_idx) if active_idx == idx) {
                 self.update_active_profile(None)?;
@@ -266,17 +446,19 @@ impl Games {
         })
     }
 
-    pub fn create_game(&mut self, name: &str, savefile_path: &Path) -> Result<()> {
+    pub fn create_game(&mut self, name: &str, savefile_paths: Vec<PathBuf>) -> Result<()> {
         if name.is_empty() {
             return Err(anyhow::anyhow!("Name can't be empty."));
         }
 
+        validate_savefile_paths(&savefile_paths)?;
+
         let path = utils::get_state_dir()?.join(name);
         utils::check_for_dup(&path)?;
         std::fs::create_dir(&path)?;
 
         let mut game = Game::new(path);
-        game.savefile_path = Some(savefile_path.to_owned());
+        game.savefile_paths = savefile_paths;
         game.write_state()?;
 
         self.pending_create = true;
@@ -300,7 +482,9 @@ impl Games {
 
     pub fn delete_selected_game(&mut self) -> Result<()> {
         if let Some(game) = self.inner.get_selected() {
-            std::fs::remove_dir_all(&game.path)?;
+            let res = std::fs::remove_dir_all(&game.path);
+            utils::log("delete game", &[&game.path], &res);
+            res?;
         }
 
         Ok(())
@@ -322,6 +506,13 @@ impl Games {
         let game = self.inner.get_selected_mut().unwrap();
         game.load_profiles()?;
         update_active_game(&game.name())?;
+        // the timestamp is only shown in the selection window, so failing to record it doesn't
+        // stop the game from being selected
+        let res = LastUsed::record(&game.path);
+        utils::log("record last use", &[&game.path], &res);
+        if let Ok(time) = res {
+            game.last_used = Some(time);
+        }
         self.active_game = self.inner.state.selected();
 
         Ok(true)
@@ -388,13 +579,19 @@ impl HandleFileSystemEvent for Games {
 
         let game = &mut games[idx];
         new_path.clone_into(&mut game.path);
+        LastUsed::rename(path, new_path)?;
+        Notes::rename(path, new_path)?;
 
         if matches!(self.active_game, Some(active_idx) if active_idx == idx) {
             for profile in &mut game.profiles.items {
                 profile.path = new_path.join(profile.name().as_ref());
-                profile
-                    .entries
-                    .update_paths(NodeId::root(), &profile.path)?;
+
+                // only the entries of the active profile are loaded
+                if profile.entries.root().is_some() {
+                    profile
+                        .entries
+                        .update_paths(NodeId::root(), &profile.path)?;
+                }
             }
 
             let new_name = new_path.file_name().unwrap().to_string_lossy();
@@ -408,6 +605,8 @@ impl HandleFileSystemEvent for Games {
         let games = &self.inner.items;
         if let Some(idx) = games.iter().position(|game| game.path == path) {
             self.inner.items.remove(idx);
+            LastUsed::remove(path)?;
+            Notes::remove(path)?;
 
             if matches!(self.active_game, Some(active_idx) if active_idx == idx) {
                 self.active_game = None;
@@ -417,3 +616,59 @@ impl HandleFileSystemEvent for Games {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Game, validate_savefile_paths};
+    use std::{fs, path::PathBuf};
+
+    #[test]
+    fn save_files_of_multiple_savefile_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut game = Game::new(dir.path().join("game"));
+        let save = dir.path().join("save");
+
+        game.savefile_paths = vec![PathBuf::from("/live/save.sl2")];
+        assert_eq!(game.save_files_in(&save), vec![save.clone()]);
+        assert!(!game.is_save(&save));
+
+        fs::write(&save, "").unwrap();
+        assert!(game.is_save(&save));
+        fs::remove_file(&save).unwrap();
+
+        game.savefile_paths = vec![
+            PathBuf::from("/live/slot1/save.dat"),
+            PathBuf::from("/live/slot2/extra.dat"),
+        ];
+        assert_eq!(
+            game.save_files_in(&save),
+            vec![save.join("save.dat"), save.join("extra.dat")]
+        );
+
+        fs::create_dir(&save).unwrap();
+        fs::write(save.join("save.dat"), "").unwrap();
+        assert!(!game.is_save(&save));
+
+        fs::write(save.join("extra.dat"), "").unwrap();
+        assert!(game.is_save(&save));
+
+        game.savefile_paths.clear();
+        assert!(!game.is_save(&save));
+    }
+
+    #[test]
+    fn savefile_paths_need_distinct_file_names() {
+        assert!(validate_savefile_paths(&[PathBuf::from("/live/save.dat")]).is_ok());
+        assert!(
+            validate_savefile_paths(&[
+                PathBuf::from("/live/slot1/save.dat"),
+                PathBuf::from("/live/slot2/save.dat"),
+            ])
+            .is_err()
+        );
+        assert!(
+            validate_savefile_paths(&[PathBuf::from("/live/save.dat"), PathBuf::from("/")])
+                .is_err()
+        );
+    }
+}
@@ -1,8 +1,9 @@
-use super::state;
+use super::{SelectionItem, state};
 use crate::entry::Entry;
-use crate::tree::Tree;
+use crate::tree::{NodeId, Tree};
 use crate::utils;
 use anyhow::Result;
+use std::collections::HashSet;
 use std::fmt::Display;
 use std::fs;
 use std::path::{MAIN_SEPARATOR, Path, PathBuf};
@@ -11,6 +12,12 @@ pub struct Profile {
     pub path: PathBuf,
     pub entries: Tree<Entry>,
     pub active_save_file: Option<PathBuf>,
+    pub pinned: HashSet<PathBuf>,
+    /// Ids of the pinned entries in the tree, resolved when the pinned entries change instead of
+    /// on every draw.
+    pinned_ids: HashSet<NodeId>,
+    pub last_used: Option<u64>,
+    pub note: Option<String>,
 }
 
 impl Profile {
@@ -19,9 +26,19 @@ impl Profile {
             path,
             entries: Tree::default(),
             active_save_file: None,
+            pinned: HashSet::new(),
+            pinned_ids: HashSet::new(),
+            last_used: None,
+            note: None,
         }
     }
 
+    fn read_state(&self) -> Option<state::ProfileState> {
+        fs::read(self.abs_path_to(".state"))
+            .ok()
+            .and_then(|s| state::ProfileState::from_bytes(&s))
+    }
+
     pub fn name(&self) -> std::borrow::Cow<'_, str> {
         self.path.file_name().unwrap().to_string_lossy()
     }
@@ -31,18 +48,20 @@ impl Profile {
             return Ok(());
         }
 
-        let state_file = self.abs_path_to(".state");
         let root = Entry::new(&self.path);
-        if let Some(state) = fs::read(state_file)
-            .ok()
-            .and_then(|s| bincode::deserialize::<state::ProfileState>(&s).ok())
-        {
+        if let Some(state) = self.read_state() {
             root.add_to_tree(&state.entries, &mut self.entries)?;
             self.active_save_file = state.active_save_file.map(|rel| self.abs_path_to(rel));
+            self.pinned = (state.pinned.into_iter())
+                .map(|rel| self.abs_path_to(rel))
+                .filter(|path| path.exists())
+                .collect();
         } else {
             root.add_to_tree(&[], &mut self.entries)?;
         }
 
+        self.resolve_pinned();
+
         Ok(())
     }
 
@@ -50,6 +69,13 @@ impl Profile {
         utils::write_atomic(&self.abs_path_to(".state"), &bincode::serialize(self)?)
     }
 
+    /// Sets the note of the profile, or removes it if `note` is empty.
+    pub fn set_note(&mut self, note: &str) -> Result<()> {
+        let note = note.trim();
+        self.note = (!note.is_empty()).then(|| note.to_owned());
+        state::Notes::set(&self.path, self.note.as_deref())
+    }
+
     pub fn get_active_save_file(&self) -> Option<PathBuf> {
         self.active_save_file.clone()
     }
@@ -65,6 +91,63 @@ impl Profile {
         Ok(())
     }
 
+    /// Updates the active save file after `path` is renamed to `new_path`, either if it is the
+    /// active save file itself or one of its ancestor folders. Returns `true` if the active save
+    /// file was updated.
+    pub fn rename_active_save_file(&mut self, path: &Path, new_path: &Path) -> Result<bool> {
+        let Some(new_active_path) = (self.active_save_file.as_ref())
+            .and_then(|active_path| utils::replace_prefix(active_path, path, new_path))
+        else {
+            return Ok(false);
+        };
+
+        self.update_active_save_file(&new_active_path)?;
+
+        Ok(true)
+    }
+
+    pub fn is_pinned(&self, path: &Path) -> bool {
+        self.pinned.contains(path)
+    }
+
+    pub fn pinned_ids(&self) -> &HashSet<NodeId> {
+        &self.pinned_ids
+    }
+
+    fn resolve_pinned(&mut self) {
+        self.pinned_ids = (self.pinned.iter())
+            .filter_map(|path| self.entries.find_by_path(path))
+            .collect();
+    }
+
+    /// Pins the entry at `path` or unpins it if it is already pinned. Returns `true` if the entry
+    /// got pinned.
+    pub fn toggle_pin(&mut self, path: &Path) -> Result<bool> {
+        let pinned = !self.pinned.remove(path) && self.pinned.insert(path.to_owned());
+        self.resolve_pinned();
+        self.write_state()?;
+
+        Ok(pinned)
+    }
+
+    /// Updates the pinned entries that are `path` or inside it after `path` is renamed to
+    /// `new_path`. The state isn't written.
+    pub fn rename_pinned(&mut self, path: &Path, new_path: &Path) {
+        self.pinned = (self.pinned.drain())
+            .map(|pinned| utils::replace_prefix(&pinned, path, new_path).unwrap_or(pinned))
+            .collect();
+        self.resolve_pinned();
+    }
+
+    /// Unpins the entries that are `path` or inside it. Returns `true` if any entry got unpinned.
+    /// The state isn't written.
+    pub fn remove_pinned(&mut self, path: &Path) -> bool {
+        let count = self.pinned.len();
+        self.pinned.retain(|pinned| !pinned.starts_with(path));
+        self.resolve_pinned();
+        count != self.pinned.len()
+    }
+
     pub fn reset_active_save_file(&mut self) -> Result<()> {
         self.active_save_file = None;
         self.write_state()?;
@@ -83,6 +166,16 @@ impl Profile {
             .to_string()
     }
 
+    /// Returns the path to show in the UI, which is relative to the profile unless `absolute` is
+    /// set.
+    pub fn display_path(&self, entry_path: &Path, absolute: bool) -> String {
+        if absolute {
+            entry_path.display().to_string()
+        } else {
+            self.rel_path_to(entry_path)
+        }
+    }
+
     pub fn get_file_rel_paths(&self, include_folders: bool) -> Vec<String> {
         let mut paths = Vec::new();
 
@@ -102,8 +195,111 @@ impl Profile {
     }
 }
 
+impl SelectionItem for Profile {
+    fn name(&self) -> std::borrow::Cow<'_, str> {
+        self.name()
+    }
+
+    fn last_used(&self) -> Option<u64> {
+        self.last_used
+    }
+
+    fn note(&self) -> Option<&str> {
+        self.note.as_deref()
+    }
+}
+
 impl Display for Profile {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.name())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Profile;
+    use crate::config::SKIP_CONFIG;
+    use std::fs;
+
+    #[test]
+    fn active_save_file_survives_ancestor_rename() {
+        // keep the test harness arguments and the user's config out of the options
+        SKIP_CONFIG.call_once(|| {});
+        let dir = tempfile::tempdir().unwrap();
+        let folder = dir.path().join("folder");
+        fs::create_dir_all(folder.join("inner")).unwrap();
+        fs::write(folder.join("inner/save.sl2"), "").unwrap();
+
+        let mut profile = Profile::new(dir.path().to_owned());
+        profile.load_entries().unwrap();
+        profile
+            .update_active_save_file(&folder.join("inner/save.sl2"))
+            .unwrap();
+
+        let new_folder = dir.path().join("renamed");
+        fs::rename(&folder, &new_folder).unwrap();
+        assert!(
+            profile
+                .rename_active_save_file(&folder, &new_folder)
+                .unwrap()
+        );
+        assert_eq!(
+            profile.get_active_save_file(),
+            Some(new_folder.join("inner/save.sl2"))
+        );
+
+        let new_file = new_folder.join("inner/renamed.sl2");
+        assert!(
+            profile
+                .rename_active_save_file(&new_folder.join("inner/save.sl2"), &new_file)
+                .unwrap()
+        );
+        assert_eq!(profile.get_active_save_file(), Some(new_file));
+
+        assert!(
+            !profile
+                .rename_active_save_file(&dir.path().join("other"), &dir.path().join("x"))
+                .unwrap()
+        );
+
+        // the state written to the disk should point to the new path
+        let mut reloaded = Profile::new(dir.path().to_owned());
+        reloaded.load_entries().unwrap();
+        assert_eq!(
+            reloaded.get_active_save_file(),
+            profile.get_active_save_file()
+        );
+    }
+
+    #[test]
+    fn pinned_entries_persist() {
+        SKIP_CONFIG.call_once(|| {});
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("folder")).unwrap();
+        fs::write(dir.path().join("folder/save.sl2"), "").unwrap();
+
+        // state written before pinned entries were introduced
+        let legacy_state =
+            bincode::serialize(&(None::<String>, Vec::<(String, ())>::new())).unwrap();
+        fs::write(dir.path().join(".state"), legacy_state).unwrap();
+
+        let mut profile = Profile::new(dir.path().to_owned());
+        profile.load_entries().unwrap();
+        assert!(profile.pinned.is_empty());
+
+        let save = dir.path().join("folder/save.sl2");
+        assert!(profile.toggle_pin(&save).unwrap());
+
+        let new_folder = dir.path().join("renamed");
+        profile.rename_pinned(&dir.path().join("folder"), &new_folder);
+        fs::rename(dir.path().join("folder"), &new_folder).unwrap();
+        profile.write_state().unwrap();
+
+        let mut reloaded = Profile::new(dir.path().to_owned());
+        reloaded.load_entries().unwrap();
+        assert!(reloaded.is_pinned(&new_folder.join("save.sl2")));
+
+        assert!(!reloaded.toggle_pin(&new_folder.join("save.sl2")).unwrap());
+        assert!(reloaded.pinned.is_empty());
+    }
+}
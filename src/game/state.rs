@@ -1,11 +1,56 @@
 use super::{Game, profile::Profile};
-use crate::tree::{NodeId, Tree};
+use crate::{
+    config::options::PopupSize,
+    tree::{NodeId, Tree},
+    utils,
+};
+use anyhow::Result;
 use serde::{Deserialize, Serialize, Serializer, ser::SerializeStruct};
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+const LAST_USED_FILE: &str = ".last_used";
+const NOTES_FILE: &str = ".notes";
+const POPUP_SIZES_FILE: &str = ".popup_sizes";
 
 #[derive(Deserialize)]
 pub struct GameState {
     pub active_profile: Option<String>,
-    pub savefile_path: Option<String>,
+    /// The first savefile path, in the same place as in the state files written before games
+    /// could have multiple save files.
+    savefile_path: Option<String>,
+    extra_savefile_paths: Vec<String>,
+}
+
+/// State files written before games could have multiple save files.
+#[derive(Deserialize)]
+struct LegacyGameState {
+    active_profile: Option<String>,
+    savefile_path: Option<String>,
+}
+
+impl GameState {
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        bincode::deserialize(bytes).ok().or_else(|| {
+            let legacy = bincode::deserialize::<LegacyGameState>(bytes).ok()?;
+
+            Some(Self {
+                active_profile: legacy.active_profile,
+                savefile_path: legacy.savefile_path,
+                extra_savefile_paths: Vec::new(),
+            })
+        })
+    }
+
+    pub fn savefile_paths(self) -> Vec<PathBuf> {
+        (self.savefile_path.into_iter())
+            .chain(self.extra_savefile_paths)
+            .map(PathBuf::from)
+            .collect()
+    }
 }
 
 impl Serialize for Game {
@@ -13,21 +58,47 @@ impl Serialize for Game {
     where
         S: Serializer,
     {
-        let mut state = serializer.serialize_struct("Game", 2)?;
+        let mut state = serializer.serialize_struct("Game", 3)?;
         let profile_name = self.get_profile().map(Profile::name);
         state.serialize_field("active_profile", &profile_name)?;
-        state.serialize_field("savefile_path", &self.savefile_path)?;
+        state.serialize_field("savefile_path", &self.savefile_paths.first())?;
+        state.serialize_field(
+            "extra_savefile_paths",
+            self.savefile_paths.get(1..).unwrap_or_default(),
+        )?;
         state.end()
     }
 }
 
-#[derive(Deserialize)]
+#[derive(Default, Serialize, Deserialize)]
 pub struct ProfileState {
     pub active_save_file: Option<String>,
     pub entries: Vec<Entry>,
+    pub pinned: Vec<String>,
 }
 
+/// State files written before pinned entries were introduced.
 #[derive(Deserialize)]
+struct LegacyProfileState {
+    active_save_file: Option<String>,
+    entries: Vec<Entry>,
+}
+
+impl ProfileState {
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        bincode::deserialize(bytes).ok().or_else(|| {
+            let legacy = bincode::deserialize::<LegacyProfileState>(bytes).ok()?;
+
+            Some(Self {
+                active_save_file: legacy.active_save_file,
+                entries: legacy.entries,
+                pinned: Vec::new(),
+            })
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
 pub struct Entry {
     pub name: String,
     pub entries: Option<Vec<Entry>>,
@@ -42,9 +113,15 @@ impl Serialize for Profile {
             .map(|id| SerializeHelper::new(id, &self.entries))
             .collect::<Vec<SerializeHelper>>();
 
-        let mut state = serializer.serialize_struct("Profile", 2)?;
+        let mut pinned = (self.pinned.iter())
+            .map(|path| self.rel_path_to(path))
+            .collect::<Vec<String>>();
+        pinned.sort_unstable();
+
+        let mut state = serializer.serialize_struct("Profile", 3)?;
         state.serialize_field("active_save_file", &self.active_save_file)?;
         state.serialize_field("entries", &entries)?;
+        state.serialize_field("pinned", &pinned)?;
         state.end()
     }
 }
@@ -76,3 +153,220 @@ impl Serialize for SerializeHelper<'_> {
         state.end()
     }
 }
+
+/// Unix timestamps of the last time games and profiles were selected, keyed by their paths
+/// relative to the state directory.
+#[derive(Default, Serialize, Deserialize)]
+pub struct LastUsed(HashMap<String, u64>);
+
+impl LastUsed {
+    pub fn read() -> Self {
+        utils::get_state_dir()
+            .ok()
+            .and_then(|dir| fs::read(dir.join(LAST_USED_FILE)).ok())
+            .and_then(|s| bincode::deserialize(&s).ok())
+            .unwrap_or_default()
+    }
+
+    fn write(&self) -> Result<()> {
+        utils::write_atomic(
+            &utils::get_state_dir()?.join(LAST_USED_FILE),
+            &bincode::serialize(self)?,
+        )
+    }
+
+    pub fn get(&self, path: &Path) -> Option<u64> {
+        self.0.get(&key(path).ok()?).copied()
+    }
+
+    /// Records the current time as the last use of the game or profile at `path` and returns it.
+    pub fn record(path: &Path) -> Result<u64> {
+        let mut last_used = Self::read();
+        let now = utils::unix_timestamp();
+        last_used.0.insert(key(path)?, now);
+        last_used.write()?;
+
+        Ok(now)
+    }
+
+    /// Moves the timestamps of the game or profile at `path`, including the ones of its
+    /// profiles, to `new_path`.
+    pub fn rename(path: &Path, new_path: &Path) -> Result<()> {
+        let (old_key, new_key) = (key(path)?, key(new_path)?);
+        let mut last_used = Self::read();
+
+        rename_keys(&mut last_used.0, &old_key, &new_key);
+
+        last_used.write()
+    }
+
+    /// Removes the timestamps of the game or profile at `path` and its profiles.
+    pub fn remove(path: &Path) -> Result<()> {
+        let old_key = key(path)?;
+        let mut last_used = Self::read();
+        remove_keys(&mut last_used.0, &old_key);
+        last_used.write()
+    }
+}
+
+/// Notes of the profiles, keyed by their paths relative to the state directory. They are kept
+/// out of the state files of the profiles so that they can be shown without reading the entries.
+#[derive(Default, Serialize, Deserialize)]
+pub struct Notes(HashMap<String, String>);
+
+impl Notes {
+    pub fn read() -> Self {
+        utils::get_state_dir()
+            .ok()
+            .and_then(|dir| fs::read(dir.join(NOTES_FILE)).ok())
+            .and_then(|s| bincode::deserialize(&s).ok())
+            .unwrap_or_default()
+    }
+
+    fn write(&self) -> Result<()> {
+        utils::write_atomic(
+            &utils::get_state_dir()?.join(NOTES_FILE),
+            &bincode::serialize(self)?,
+        )
+    }
+
+    pub fn get(&self, path: &Path) -> Option<String> {
+        self.0.get(&key(path).ok()?).cloned()
+    }
+
+    /// Sets the note of the profile at `path`, or removes it if `note` is `None`.
+    pub fn set(path: &Path, note: Option<&str>) -> Result<()> {
+        let mut notes = Self::read();
+
+        match note {
+            Some(note) => notes.0.insert(key(path)?, note.to_owned()),
+            None => notes.0.remove(&key(path)?),
+        };
+
+        notes.write()
+    }
+
+    /// Moves the notes of the profile at `path`, or of the profiles of the game at `path`, to
+    /// `new_path`.
+    pub fn rename(path: &Path, new_path: &Path) -> Result<()> {
+        let (old_key, new_key) = (key(path)?, key(new_path)?);
+        let mut notes = Self::read();
+        rename_keys(&mut notes.0, &old_key, &new_key);
+        notes.write()
+    }
+
+    /// Removes the notes of the profile at `path`, or of the profiles of the game at `path`.
+    pub fn remove(path: &Path) -> Result<()> {
+        let old_key = key(path)?;
+        let mut notes = Self::read();
+        remove_keys(&mut notes.0, &old_key);
+        notes.write()
+    }
+}
+
+/// Sizes the popups were last resized to, restored on startup with the `remember_popup_sizes`
+/// option.
+#[derive(Default, Serialize, Deserialize)]
+pub struct PopupSizes {
+    pub help: Option<PopupSize>,
+    pub fuzzy_finder: Option<PopupSize>,
+}
+
+impl PopupSizes {
+    pub fn read() -> Self {
+        utils::get_state_dir()
+            .ok()
+            .and_then(|dir| fs::read(dir.join(POPUP_SIZES_FILE)).ok())
+            .and_then(|s| bincode::deserialize(&s).ok())
+            .unwrap_or_default()
+    }
+
+    /// Applies `f` to the stored sizes and writes them back.
+    pub fn update(f: impl FnOnce(&mut Self)) -> Result<()> {
+        let mut sizes = Self::read();
+        f(&mut sizes);
+        utils::write_atomic(
+            &utils::get_state_dir()?.join(POPUP_SIZES_FILE),
+            &bincode::serialize(&sizes)?,
+        )
+    }
+}
+
+/// Replaces the `old_key` prefix of the keys with `new_key`.
+fn rename_keys<T>(map: &mut HashMap<String, T>, old_key: &str, new_key: &str) {
+    *map = std::mem::take(map)
+        .into_iter()
+        .map(|(key, value)| {
+            match utils::replace_prefix(Path::new(&key), Path::new(old_key), Path::new(new_key)) {
+                Some(new_key) => (new_key.to_string_lossy().into(), value),
+                None => (key, value),
+            }
+        })
+        .collect();
+}
+
+/// Removes the keys that are `old_key` or start with it.
+fn remove_keys<T>(map: &mut HashMap<String, T>, old_key: &str) {
+    map.retain(|key, _| !Path::new(key).starts_with(old_key));
+}
+
+fn key(path: &Path) -> Result<String> {
+    let rel_path: PathBuf = utils::get_relative_path(&utils::get_state_dir()?, path)?;
+    Ok(rel_path.to_string_lossy().into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GameState, remove_keys, rename_keys};
+    use std::{collections::HashMap, path::PathBuf};
+
+    #[test]
+    fn game_state_from_bytes() {
+        let state = (
+            Some("profile"),
+            Some("/live/slot1.dat"),
+            vec!["/live/slot2.dat"],
+        );
+        let state = GameState::from_bytes(&bincode::serialize(&state).unwrap()).unwrap();
+        assert_eq!(state.active_profile.as_deref(), Some("profile"));
+        assert_eq!(
+            state.savefile_paths(),
+            vec![
+                PathBuf::from("/live/slot1.dat"),
+                PathBuf::from("/live/slot2.dat")
+            ]
+        );
+
+        // state written before games could have multiple save files
+        let legacy_state = (Some("profile"), Some("/live/save.dat"));
+        let state = GameState::from_bytes(&bincode::serialize(&legacy_state).unwrap()).unwrap();
+        assert_eq!(state.active_profile.as_deref(), Some("profile"));
+        assert_eq!(
+            state.savefile_paths(),
+            vec![PathBuf::from("/live/save.dat")]
+        );
+
+        assert!(GameState::from_bytes(&[]).is_none());
+    }
+
+    #[test]
+    fn keys_follow_renames_and_deletions() {
+        let mut notes = HashMap::from([
+            (String::from("game/profile"), "profile note"),
+            (String::from("game/profile 2"), "other note"),
+            (String::from("other game/profile"), "unrelated note"),
+        ]);
+
+        rename_keys(&mut notes, "game/profile", "game/renamed");
+        assert_eq!(notes.get("game/renamed"), Some(&"profile note"));
+        assert_eq!(notes.get("game/profile 2"), Some(&"other note"));
+
+        rename_keys(&mut notes, "game", "new game");
+        assert_eq!(notes.get("new game/renamed"), Some(&"profile note"));
+        assert_eq!(notes.get("new game/profile 2"), Some(&"other note"));
+
+        remove_keys(&mut notes, "new game");
+        assert_eq!(notes.len(), 1);
+        assert_eq!(notes.get("other game/profile"), Some(&"unrelated note"));
+    }
+}
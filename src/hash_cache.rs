@@ -0,0 +1,63 @@
+use crate::entry::Metadata;
+use std::{
+    collections::HashMap,
+    fs,
+    hash::{DefaultHasher, Hasher},
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+struct Cached {
+    modified: Option<SystemTime>,
+    len: u64,
+    hash: u64,
+}
+
+/// Hashes of the contents of files, which are recomputed once the modification time or the size
+/// of the file changes.
+#[derive(Default)]
+pub struct HashCache(HashMap<PathBuf, Cached>);
+
+impl HashCache {
+    /// Returns the hash of the contents of the file at `path` with the given `metadata`, or `None`
+    /// if it can't be read. The file is only read if it changed since it was last hashed.
+    pub fn get(&mut self, path: &Path, metadata: Metadata) -> Option<u64> {
+        if let Some(cached) = self.0.get(path)
+            && cached.modified == metadata.modified
+            && cached.len == metadata.len
+        {
+            return Some(cached.hash);
+        }
+
+        let mut hasher = DefaultHasher::new();
+        hasher.write(&fs::read(path).ok()?);
+        let hash = hasher.finish();
+
+        self.0.insert(
+            path.to_path_buf(),
+            Cached {
+                modified: metadata.modified,
+                len: metadata.len,
+                hash,
+            },
+        );
+
+        Some(hash)
+    }
+
+    /// Returns the combined hash of the files of a save, or `None` if any of them can't be read.
+    pub fn get_save(&mut self, files: &[(PathBuf, Metadata)]) -> Option<u64> {
+        let mut hasher = DefaultHasher::new();
+
+        for (file, metadata) in files {
+            hasher.write_u64(self.get(file, *metadata)?);
+        }
+
+        Some(hasher.finish())
+    }
+
+    /// Drops the hashes of the files for which `keep` returns false.
+    pub fn retain(&mut self, mut keep: impl FnMut(&Path) -> bool) {
+        self.0.retain(|path, _| keep(path));
+    }
+}
@@ -1,15 +1,20 @@
-use crate::{config::KEY_BINDINGS, ui::Scroller};
+use crate::{
+    config::{KEY_BINDINGS, OPTIONS, options::PopupSize},
+    game::state::PopupSizes,
+    input::Mode,
+    ui::Scroller,
+};
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use std::ops::Deref;
 
-const DESCRIPTIONS_LEN: usize = 37;
+const DESCRIPTIONS_LEN: usize = 69;
 const DESCRIPTIONS: [&str; DESCRIPTIONS_LEN] = [
     "Go one line downward",                                 // On Down
     "Go one line upward",                                   // On Up
     "Close fold",                                           // On Left
     "Open fold",                                            // On Right
-    "Jump to the first line",                               // Select First
-    "Jump to the last line",                                // Select Last
+    "Jump to the first line, or the line typed before",     // Select First
+    "Jump to the last line, or the line typed before",      // Select Last
     "Jump to the folder below",                             // Down Directory
     "Jump to the folder above",                             // Up Directory
     "Jump to the parent folder",                            // Jump To Parent
@@ -17,29 +22,61 @@ const DESCRIPTIONS: [&str; DESCRIPTIONS_LEN] = [
     "Load a random save file",                              // Load Random Save File
     "Load the active save file",                            // Load Active Save File
     "Mark the selected save file as active",                // Mark Save File
+    "Clear the active save file",                           // Unmark Active
     "Import save file into the current folder",             // Import Save File
     "Import save file to the top level",                    // Import Save File Top Level
     "Import new save file and overwrite the selected file", // Replace Save File
+    "Overwrite the selected file with the active save",     // Replace With Active Save File
     "Delete the selected file/folder",                      // Delete File
     "Create folder",                                        // Create Folder
     "Create folder in the top level",                       // Create Folder Top Level
     "Rename the selected file/folder",                      // Rename
+    "Rename the marked entries using a template",           // Bulk Rename
+    "Add or remove the configured extension",               // Toggle Extension
     "Move the marked entries into the current folder",      // Move Entries
     "Move the marked entries to the top level",             // Move Entries Top Level
+    "Yank the marked or selected entries",                  // Yank Entries
+    "Cut the marked or selected entries",                   // Cut Entries
+    "Paste the yanked or cut entries into the folder",      // Paste Entries
+    "Merge the marked folder into the selected folder",     // Merge Folders
+    "Delete the folders without any save files",            // Prune Empty Folders
     "Swap the selected entry with its above sibling",       // Move Up
     "Swap the selected entry with its below sibling",       // Move Below
+    "Open the fold and the folds of its direct children",   // Expand One
     "Open all folds",                                       // Open All Folds
     "Close all folds",                                      // Close All Folds
-    "Open game selection window",                           // Open Game Window
-    "Open profile selection window",                        // Open Profile Window
+    "Close the folds outside of the selected branch",       // Focus Folder
+    "Toggle flat view of the save files",                   // Toggle Flat View
+    "Toggle game selection window",                         // Open Game Window
+    "Toggle profile selection window",                      // Open Profile Window
+    "Switch to the next game",                              // Next Game
+    "Switch to the previous game",                          // Previous Game
+    "Switch to the next profile",                           // Next Profile
+    "Switch to the previous profile",                       // Previous Profile
     "Open help window",                                     // Toggle Help
     "Enter search pattern",                                 // Enter Search
     "Repeat the latest search",                             // Repeat Last Search
     "Repeat the latest search backward",                    // Repeat Last Search Backward
+    "Hide the entries that don't match a pattern",          // Enter Filter
+    "Write the search or filter matches to a file",         // Export Matches
     "Open fuzzy finder",                                    // Open Fuzzy Finder
     "Open global fuzzy finder",                             // Open Fuzzy Finder Global
+    "Open fuzzy finder over the lines of text save files",  // Search Contents
     "Mark the selected entry",                              // Mark Entry
-    "Unmark all marked entries",                            // Reset
+    "Mark the entries that match the last search",          // Mark Search Matches
+    "Pin/unpin the selected entry",                         // Pin Entry
+    "Open fuzzy finder over the pinned entries",            // Open Pinned Picker
+    "Compare the two marked files with the diff command",   // Diff Marked
+    "Swap the contents of the two marked files",            // Swap Contents
+    "Copy the selected entry into the other profiles",      // Broadcast To Profiles
+    "Toggle the log of the file system events",             // Toggle Event Log
+    "Show the disk usage of the profile",                   // Show Disk Usage
+    "Open a shell in the profile folder",                   // Open Shell
+    "Open the config file in the editor",                   // Edit Config
+    "Reload the config file",                               // Reload Config
+    "Toggle absolute paths in messages and prompts",        // Toggle Absolute Paths
+    "Toggle read-only mode",                                // Toggle Read Only
+    "Unmark all marked entries and clear the message",      // Reset
     "Quit application",                                     // Quit
 ];
 
@@ -53,15 +90,37 @@ const GAME_SELECTION_DESCRIPTIONS: [&str; GAME_SELECTION_DESCRIPTIONS_LEN] = [
     " - Abort",
 ];
 
-const PROFILE_SELECTION_DESCRIPTIONS_LEN: usize = 5;
+const PROFILE_SELECTION_DESCRIPTIONS_LEN: usize = 6;
 const PROFILE_SELECTION_DESCRIPTIONS: [&str; PROFILE_SELECTION_DESCRIPTIONS_LEN] = [
     " - Create, ",
     " - Rename, ",
+    " - Note, ",
     " - Delete, ",
     " - Select, ",
     " - Abort",
 ];
 
+const CONFIRMATION_DESCRIPTIONS_LEN: usize = 6;
+const CONFIRMATION_DESCRIPTIONS: [&str; CONFIRMATION_DESCRIPTIONS_LEN] = [
+    "Confirm",
+    "Cancel",
+    "Scroll up",
+    "Scroll down",
+    "Go to top",
+    "Go to bottom",
+];
+
+const HELP_DESCRIPTIONS_LEN: usize = 7;
+const HELP_DESCRIPTIONS: [&str; HELP_DESCRIPTIONS_LEN] = [
+    "Scroll up",
+    "Scroll down",
+    "Go to top",
+    "Go to bottom",
+    "Grow the window",
+    "Shrink the window",
+    "Close the window",
+];
+
 fn key_event_to_string(key_event: &KeyEvent) -> String {
     let key_code = match key_event.code {
         KeyCode::Backspace => "backspace",
@@ -111,10 +170,29 @@ fn key_event_to_string(key_event: &KeyEvent) -> String {
 
 const HELP_ENTRY: (String, &str) = (String::new(), "");
 
+macro_rules! generate_entries {
+    ($entries: expr, $bindings: expr, $descriptions: ident) => {
+        for (key, command) in $bindings {
+            let idx = *command as usize;
+
+            if !$entries[idx].0.is_empty() {
+                $entries[idx].0.push_str(", ");
+            }
+            $entries[idx].0.push_str(&key_event_to_string(key));
+        }
+
+        for (idx, (_, desc)) in $entries.iter_mut().enumerate() {
+            *desc = $descriptions[idx];
+        }
+    };
+}
+
 pub struct Bindings {
     pub general: [(String, &'static str); DESCRIPTIONS_LEN],
     pub game_selection: [(String, &'static str); GAME_SELECTION_DESCRIPTIONS_LEN],
     pub profile_selection: [(String, &'static str); PROFILE_SELECTION_DESCRIPTIONS_LEN],
+    pub confirmation: [(String, &'static str); CONFIRMATION_DESCRIPTIONS_LEN],
+    pub help: [(String, &'static str); HELP_DESCRIPTIONS_LEN],
 }
 
 impl Default for Bindings {
@@ -123,36 +201,32 @@ impl Default for Bindings {
             general: [HELP_ENTRY; DESCRIPTIONS_LEN],
             game_selection: [HELP_ENTRY; GAME_SELECTION_DESCRIPTIONS_LEN],
             profile_selection: [HELP_ENTRY; PROFILE_SELECTION_DESCRIPTIONS_LEN],
+            confirmation: [HELP_ENTRY; CONFIRMATION_DESCRIPTIONS_LEN],
+            help: [HELP_ENTRY; HELP_DESCRIPTIONS_LEN],
         };
 
-        macro_rules! generate_entries {
-            ($entries: expr, $bindings: expr, $descriptions: ident) => {
-                for (key, command) in &$bindings {
-                    let idx = *command as usize;
-
-                    if !$entries[idx].0.is_empty() {
-                        $entries[idx].0.push_str(", ");
-                    }
-                    $entries[idx].0.push_str(&key_event_to_string(key));
-                }
-
-                for (idx, (_, desc)) in $entries.iter_mut().enumerate() {
-                    *desc = $descriptions[idx];
-                }
-            };
-        }
+        let key_bindings = KEY_BINDINGS.get();
+        let general = (key_bindings.general.iter())
+            .filter(|(key, _)| !key_bindings.tree.contains_key(*key))
+            .chain(&key_bindings.tree);
 
-        generate_entries!(help.general, KEY_BINDINGS.general, DESCRIPTIONS);
+        generate_entries!(help.general, general, DESCRIPTIONS);
         generate_entries!(
             help.game_selection,
-            KEY_BINDINGS.game_selection,
+            &key_bindings.game_selection,
             GAME_SELECTION_DESCRIPTIONS
         );
         generate_entries!(
             help.profile_selection,
-            KEY_BINDINGS.profile_selection,
+            &key_bindings.profile_selection,
             PROFILE_SELECTION_DESCRIPTIONS
         );
+        generate_entries!(
+            help.confirmation,
+            &key_bindings.confirmation,
+            CONFIRMATION_DESCRIPTIONS
+        );
+        generate_entries!(help.help, &key_bindings.help, HELP_DESCRIPTIONS);
 
         for (keys, _) in &mut help.general {
             *keys = format!("{keys:14}  ");
@@ -162,6 +236,62 @@ impl Default for Bindings {
     }
 }
 
+impl Bindings {
+    /// Returns the bindings of `section` in the format of the general ones. The tree has no
+    /// bindings of its own as they are listed with the general ones.
+    pub fn section(&self, section: Section) -> Vec<(String, &'static str)> {
+        let entries: &[(String, &'static str)] = match section {
+            Section::Tree => &[],
+            Section::GameSelection => &self.game_selection,
+            Section::ProfileSelection => &self.profile_selection,
+            Section::Confirmation => &self.confirmation,
+            Section::Help => &self.help,
+        };
+
+        // the selection descriptions are written to be listed in a single line
+        entries
+            .iter()
+            .map(|(keys, desc)| {
+                let desc = desc.trim_start_matches(" - ").trim_end_matches(", ");
+                (format!("{keys:14}  "), desc)
+            })
+            .collect()
+    }
+
+    /// Returns the general bindings without the bindings of the tree, which are merged into them
+    /// in `general`.
+    pub fn general_without_tree() -> Vec<(String, &'static str)> {
+        let mut general = [HELP_ENTRY; DESCRIPTIONS_LEN];
+        generate_entries!(general, &KEY_BINDINGS.get().general, DESCRIPTIONS);
+
+        general
+            .into_iter()
+            .map(|(keys, desc)| (format!("{keys:14}  "), desc))
+            .collect()
+    }
+
+    /// Returns the bindings that only apply in the tree, in the format of the general ones.
+    pub fn tree() -> Vec<(String, &'static str)> {
+        (KEY_BINDINGS.get().tree.iter())
+            .map(|(key, command)| {
+                let keys = format!("{:14}  ", key_event_to_string(key));
+                (keys, DESCRIPTIONS[*command as usize])
+            })
+            .collect()
+    }
+
+    /// Returns the bindings that only apply while the game named `name` is selected, in the
+    /// format of the general ones.
+    pub fn game(name: &str) -> Vec<(String, &'static str)> {
+        (KEY_BINDINGS.get().game.get(name).into_iter().flatten())
+            .map(|(key, command)| {
+                let keys = format!("{:14}  ", key_event_to_string(key));
+                (keys, DESCRIPTIONS[*command as usize])
+            })
+            .collect()
+    }
+}
+
 impl Deref for Bindings {
     type Target = [(String, &'static str); DESCRIPTIONS_LEN];
 
@@ -170,15 +300,85 @@ impl Deref for Bindings {
     }
 }
 
+/// Part of the app the help window is opened in, whose bindings are listed before the general
+/// ones.
+#[derive(Default, Clone, Copy, PartialEq)]
+pub enum Section {
+    #[default]
+    Tree,
+    GameSelection,
+    ProfileSelection,
+    Confirmation,
+    /// The help window itself, which is never the section it is opened in.
+    Help,
+}
+
+impl Section {
+    pub fn title(self) -> &'static str {
+        match self {
+            Section::Tree => "Tree",
+            Section::GameSelection => "Game selection",
+            Section::ProfileSelection => "Profile selection",
+            Section::Confirmation => "Confirmation",
+            Section::Help => "Help window",
+        }
+    }
+}
+
+impl From<&Mode> for Section {
+    fn from(mode: &Mode) -> Self {
+        match mode {
+            Mode::GameSelection | Mode::GameCreation | Mode::GameRenaming => Section::GameSelection,
+            Mode::ProfileSelection
+            | Mode::ProfileCreation
+            | Mode::ProfileRenaming
+            | Mode::ProfileNoteEditing => Section::ProfileSelection,
+            Mode::Confirmation(_) => Section::Confirmation,
+            _ => Section::Tree,
+        }
+    }
+}
+
 #[derive(Default)]
 pub struct Help {
     pub bindings: Bindings,
     pub visible: bool,
+    pub section: Section,
     pub scroller: Scroller,
+    /// Size set with the `grow` and `shrink` commands, the `help_size` option otherwise.
+    pub size: Option<PopupSize>,
 }
 
 impl Help {
     pub fn toggle(&mut self) {
         self.visible = !self.visible;
     }
+
+    /// Opens the window with the bindings of the part of the app that `mode` belongs to listed
+    /// first.
+    pub fn open(&mut self, mode: &Mode) {
+        let section = Section::from(mode);
+
+        if section != self.section {
+            self.section = section;
+            self.scroller.scroll_top();
+        }
+
+        self.visible = true;
+    }
+
+    pub fn size(&self) -> PopupSize {
+        self.size.unwrap_or(OPTIONS.get().help_size)
+    }
+
+    pub fn resize(&mut self, grow: bool) {
+        self.size
+            .get_or_insert(OPTIONS.get().help_size)
+            .resize(grow);
+
+        if OPTIONS.get().remember_popup_sizes {
+            // losing the size is harmless, so the popup isn't interrupted with an error
+            let _ = PopupSizes::update(|sizes| sizes.help = self.size);
+        }
+    }
 }
@@ -7,6 +7,7 @@ use crate::{
         Command, ConfirmationCommand, GameSelectionCommand, HelpCommand, ProfileSelectionCommand,
     },
     config::{KEY_BINDINGS, OPTIONS},
+    event_log::EventLog,
     fuzzy_finder::FuzzyFinder,
     game::{
         Games,
@@ -16,6 +17,7 @@ use crate::{
     message::set_msg_if_error,
     search::Direction,
     ui::confirmation::Context as ConfirmationContext,
+    utils,
 };
 use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
 
@@ -28,8 +30,13 @@ pub fn handle_event(key: KeyEvent, app: &mut App) -> bool {
         return handle_key_help_mode(key, &mut app.help);
     }
 
+    if app.event_log.visible {
+        return handle_key_event_log_mode(key, &mut app.event_log);
+    }
+
     match &app.mode {
-        Mode::Normal if !app.fuzzy_finder.is_active() => return handle_key_normal_mode(key, app),
+        _ if app.fuzzy_finder.is_active() => handle_key_editing_mode(key, app),
+        Mode::Normal => return handle_key_normal_mode(key, app),
         Mode::ProfileSelection => return handle_key_profile_selection_mode(key, app),
         Mode::GameSelection => return handle_key_game_selection_mode(key, app),
         Mode::GameCreation => return handle_key_game_creation_mode(key, app),
@@ -41,12 +48,23 @@ pub fn handle_event(key: KeyEvent, app: &mut App) -> bool {
 }
 
 fn handle_key_normal_mode(key: KeyEvent, app: &mut App) -> bool {
-    if let Some(command) = KEY_BINDINGS.get(&key) {
+    let game = app.games.get_game().map(|game| game.name().into_owned());
+    let count = app.count.take();
+
+    if let Some(command) = KEY_BINDINGS.get().get_in_tree(game.as_deref(), &key) {
+        if app.read_only && command.is_mutating() {
+            app.warn_read_only();
+            return false;
+        }
+
         match command {
             Command::OnDown => app.on_down(),
             Command::OnUp => app.on_up(),
             Command::OnLeft => app.on_left(),
             Command::OnRight => app.on_right(),
+            Command::SelectFirst | Command::SelectLast if let Some(count) = count => {
+                app.go_to_line(count);
+            }
             Command::SelectFirst => app.select_first(),
             Command::SelectLast => app.select_last(),
             Command::DownDirectory => app.down_directory(),
@@ -56,31 +74,86 @@ fn handle_key_normal_mode(key: KeyEvent, app: &mut App) -> bool {
             Command::LoadRandomSaveFile => app.load_random_save_file(),
             Command::LoadActiveSaveFile => app.load_active_save_file(),
             Command::MarkSaveFile => app.mark_selected_save_file(),
+            Command::UnmarkActive => app.unmark_active_save_file(),
             Command::ImportSaveFile => app.import_save_file(false),
             Command::ImportSaveFileTopLevel => app.import_save_file(true),
             Command::ReplaceSaveFile => app.prompt_for_confirmation(ConfirmationContext::Replacing),
+            Command::ReplaceWithActiveSaveFile => {
+                app.prompt_for_confirmation(ConfirmationContext::ReplacingWithActive);
+            }
+            Command::DeleteFile if OPTIONS.get().delete_requires_double_press => {
+                app.delete_on_double_press();
+            }
             Command::DeleteFile => app.prompt_for_confirmation(ConfirmationContext::Deletion),
             Command::CreateFolder => app.take_input(Mode::FolderCreation(false)),
             Command::CreateFolderTopLevel => app.take_input(Mode::FolderCreation(true)),
             Command::Rename => app.enter_renaming(),
+            Command::BulkRename => app.enter_bulk_renaming(),
+            Command::ToggleExtension => set_msg_if_error!(app.message, app.toggle_extension()),
             Command::MoveEntries => app.move_entries(false),
             Command::MoveEntriesTopLevel => app.move_entries(true),
+            Command::YankEntries => app.yank_entries(false),
+            Command::CutEntries => app.yank_entries(true),
+            Command::PasteEntries => set_msg_if_error!(app.message, app.paste_entries()),
+            Command::MergeFolders => {
+                let res = app.merge_folders();
+                set_msg_if_error!(app.message, res);
+            }
+            Command::PruneEmptyFolders => app.start_pruning(),
             Command::MoveUp => app.move_up(),
             Command::MoveDown => app.move_down(),
+            Command::ExpandOne => app.expand_one(),
             Command::OpenAllFolds => app.open_all_folds(),
             Command::CloseAllFolds => app.close_all_folds(),
+            Command::FocusFolder => app.focus_folder(),
+            Command::ToggleFlatView => app.toggle_flat_view(),
             Command::OpenGameWindow => app.open_game_window(),
             Command::OpenProfileWindow => app.open_profile_window(),
-            Command::ToggleHelp => app.help.toggle(),
+            Command::NextGame => app.cycle_game(true),
+            Command::PreviousGame => app.cycle_game(false),
+            Command::NextProfile => app.cycle_profile(true),
+            Command::PreviousProfile => app.cycle_profile(false),
+            Command::ToggleHelp => app.help.open(&app.mode),
             Command::EnterSearch => app.search_new_pattern(),
             Command::RepeatLastSearch => app.repeat_search(),
             Command::RepeatLastSearchBackward => app.repeat_search_reverse(),
+            Command::EnterFilter => app.enter_filter(),
+            Command::ExportMatches => app.start_match_export(),
             Command::OpenFuzzyFinder => app.open_fuzzy_finder(false),
             Command::OpenFuzzyFinderGlobal => app.open_fuzzy_finder(true),
+            Command::SearchContents => app.open_content_search(),
             Command::MarkEntry => app.mark_entry(),
-            Command::Reset => app.tree_state.marked.clear(),
+            Command::MarkSearchMatches => app.mark_search_matches(),
+            Command::PinEntry => set_msg_if_error!(app.message, app.toggle_pin()),
+            Command::OpenPinnedPicker => app.open_pinned_picker(),
+            Command::DiffMarked => set_msg_if_error!(app.message, app.diff_marked()),
+            Command::SwapContents => set_msg_if_error!(app.message, app.swap_contents()),
+            Command::BroadcastToProfiles => app.broadcast_to_profiles(),
+            Command::ToggleEventLog => app.event_log.toggle(),
+            Command::ShowDiskUsage => app.show_disk_usage(),
+            Command::OpenShell => app.open_shell(),
+            Command::EditConfig => {
+                let res = app.edit_config();
+                set_msg_if_error!(app.message, res);
+            }
+            Command::ReloadConfig => app.reload_config(),
+            Command::ToggleAbsolutePaths => app.toggle_absolute_paths(),
+            Command::ToggleReadOnly => app.toggle_read_only(),
+            Command::Reset => {
+                app.tree_state.marked.clear();
+                app.message.clear();
+                app.clear_filter();
+            }
             Command::Quit => return true,
         }
+    } else if key.code == KeyCode::Enter && key.modifiers.is_empty() {
+        app.on_enter();
+    } else if let KeyCode::Char(c) = key.code
+        && let Some(digit) = c.to_digit(10)
+        && key.modifiers.is_empty()
+    {
+        let count = count.unwrap_or(0);
+        app.count = Some(count.saturating_mul(10).saturating_add(digit as usize));
     }
 
     false
@@ -89,7 +162,12 @@ fn handle_key_normal_mode(key: KeyEvent, app: &mut App) -> bool {
 fn handle_key_game_selection_mode(key: KeyEvent, app: &mut App) -> bool {
     let games = &mut app.games.inner;
 
-    if let Some(command) = KEY_BINDINGS.game_selection.get(&key) {
+    if let Some(command) = KEY_BINDINGS.get().game_selection.get(&key) {
+        if app.read_only && command.is_mutating() {
+            app.warn_read_only();
+            return false;
+        }
+
         match command {
             GameSelectionCommand::Create => {
                 app.game_creation = CreatingGame::default();
@@ -114,18 +192,21 @@ fn handle_key_game_selection_mode(key: KeyEvent, app: &mut App) -> bool {
             }
             GameSelectionCommand::Abort => abort(app),
         }
-    } else if let Some(command) = KEY_BINDINGS.get(&key) {
+    } else if let Some(command) = KEY_BINDINGS.get().get(&key) {
         match command {
-            Command::OnDown => games.next(),
-            Command::OnUp => games.previous(),
+            Command::OnDown => games.next(OPTIONS.get().wrap_lists),
+            Command::OnUp => games.previous(OPTIONS.get().wrap_lists),
             Command::SelectFirst => games.select_first(),
             Command::SelectLast => games.select_last(),
             Command::EnterSearch => app.search_new_pattern(),
             Command::RepeatLastSearch => app.repeat_search(),
             Command::RepeatLastSearchBackward => app.repeat_search_reverse(),
+            Command::OpenFuzzyFinder => app.open_fuzzy_finder(false),
+            Command::OpenGameWindow => app.close_selection_window(),
             Command::OpenProfileWindow if app.games.get_game().is_some() => {
                 app.open_profile_window();
             }
+            Command::ToggleHelp => app.help.open(&app.mode),
             Command::Quit => return true,
             _ => (),
         }
@@ -140,7 +221,7 @@ fn handle_key_game_creation_mode(key: KeyEvent, app: &mut App) -> bool {
     match &mut state.step {
         Step::EnterName | Step::EnterPath => handle_key_editing_mode(key, app),
         Step::PresetOrManual(use_preset) => {
-            if let Some(command) = KEY_BINDINGS.game_selection.get(&key) {
+            if let Some(command) = KEY_BINDINGS.get().game_selection.get(&key) {
                 match command {
                     GameSelectionCommand::Select => {
                         if *use_preset {
@@ -153,9 +234,8 @@ fn handle_key_game_creation_mode(key: KeyEvent, app: &mut App) -> bool {
                                     &app.games
                                         .inner
                                         .get_selected()
-                                        .and_then(|game| game.savefile_path.clone())
-                                        .unwrap_or_default()
-                                        .to_string_lossy(),
+                                        .map(|game| utils::join_paths(&game.savefile_paths))
+                                        .unwrap_or_default(),
                                 );
                             }
 
@@ -177,7 +257,7 @@ fn handle_key_game_creation_mode(key: KeyEvent, app: &mut App) -> bool {
                     }
                     _ => (),
                 }
-            } else if let Some(command) = KEY_BINDINGS.get(&key) {
+            } else if let Some(command) = KEY_BINDINGS.get().get(&key) {
                 match command {
                     Command::OnLeft | Command::OnRight => *use_preset = !*use_preset,
                     Command::Quit => return true,
@@ -186,17 +266,12 @@ fn handle_key_game_creation_mode(key: KeyEvent, app: &mut App) -> bool {
             }
         }
         Step::Presets(presets) => {
-            if let Some(command) = KEY_BINDINGS.game_selection.get(&key) {
+            if let Some(command) = KEY_BINDINGS.get().game_selection.get(&key) {
                 match command {
                     GameSelectionCommand::Select => {
                         let selected_preset = presets.get_selected().unwrap();
-                        let paths = if matches!(presets.state.selected(), Some(0)) {
-                            selected_preset.get_from_documents_dir()
-                        } else {
-                            selected_preset.get_from_data_dir()
-                        };
 
-                        match paths {
+                        match selected_preset.get_savefile_locations() {
                             Ok(paths) => {
                                 state.step =
                                     Step::SaveFileLocations(StatefulList::with_items(paths));
@@ -209,10 +284,10 @@ fn handle_key_game_creation_mode(key: KeyEvent, app: &mut App) -> bool {
                     GameSelectionCommand::Abort => state.step = Step::PresetOrManual(true),
                     _ => (),
                 }
-            } else if let Some(command) = KEY_BINDINGS.get(&key) {
+            } else if let Some(command) = KEY_BINDINGS.get().get(&key) {
                 match command {
-                    Command::OnDown => presets.next(),
-                    Command::OnUp => presets.previous(),
+                    Command::OnDown => presets.next(true),
+                    Command::OnUp => presets.previous(true),
                     Command::SelectFirst => presets.select_first(),
                     Command::SelectLast => presets.select_last(),
                     Command::EnterSearch => app.search_new_pattern(),
@@ -224,7 +299,7 @@ fn handle_key_game_creation_mode(key: KeyEvent, app: &mut App) -> bool {
             }
         }
         Step::SaveFileLocations(paths) => {
-            if let Some(command) = KEY_BINDINGS.game_selection.get(&key) {
+            if let Some(command) = KEY_BINDINGS.get().game_selection.get(&key) {
                 match command {
                     GameSelectionCommand::Select => {
                         if let Some(path) = paths.get_selected() {
@@ -232,12 +307,12 @@ fn handle_key_game_creation_mode(key: KeyEvent, app: &mut App) -> bool {
                                 app.message,
                                 if state.edit {
                                     let game = app.games.inner.get_selected_mut().unwrap();
-                                    game.set_savefile_path(path)
+                                    game.set_savefile_paths(vec![PathBuf::from(path)])
                                 } else {
                                     Games::create_game(
                                         &mut app.games,
                                         state.name.as_ref().unwrap(),
-                                        &PathBuf::from(path),
+                                        vec![PathBuf::from(path)],
                                     )
                                 }
                             );
@@ -248,10 +323,10 @@ fn handle_key_game_creation_mode(key: KeyEvent, app: &mut App) -> bool {
                     GameSelectionCommand::Abort => state.load_presets(),
                     _ => (),
                 }
-            } else if let Some(command) = KEY_BINDINGS.get(&key) {
+            } else if let Some(command) = KEY_BINDINGS.get().get(&key) {
                 match command {
-                    Command::OnDown => paths.next(),
-                    Command::OnUp => paths.previous(),
+                    Command::OnDown => paths.next(true),
+                    Command::OnUp => paths.previous(true),
                     Command::SelectFirst => paths.select_first(),
                     Command::SelectLast => paths.select_last(),
                     Command::EnterSearch => app.search_new_pattern(),
@@ -272,7 +347,12 @@ fn handle_key_profile_selection_mode(key: KeyEvent, app: &mut App) -> bool {
         return false;
     };
 
-    if let Some(command) = KEY_BINDINGS.profile_selection.get(&key) {
+    if let Some(command) = KEY_BINDINGS.get().profile_selection.get(&key) {
+        if app.read_only && command.is_mutating() {
+            app.warn_read_only();
+            return false;
+        }
+
         match command {
             ProfileSelectionCommand::Create => app.take_input(Mode::ProfileCreation),
             ProfileSelectionCommand::Rename => {
@@ -282,22 +362,32 @@ fn handle_key_profile_selection_mode(key: KeyEvent, app: &mut App) -> bool {
                     app.footer_input.as_mut().unwrap().set_text(&name);
                 }
             }
+            ProfileSelectionCommand::EditNote => {
+                if let Some(profile) = profiles.get_selected() {
+                    let note = profile.note.clone().unwrap_or_default();
+                    app.take_input(Mode::ProfileNoteEditing);
+                    app.footer_input.as_mut().unwrap().set_text(&note);
+                }
+            }
             ProfileSelectionCommand::Delete => {
                 app.prompt_for_confirmation(ConfirmationContext::ProfileDeletion);
             }
             ProfileSelectionCommand::Select => app.confirm_profile_selection(),
             ProfileSelectionCommand::Abort => abort(app),
         }
-    } else if let Some(command) = KEY_BINDINGS.get(&key) {
+    } else if let Some(command) = KEY_BINDINGS.get().get(&key) {
         match command {
-            Command::OnDown => profiles.next(),
-            Command::OnUp => profiles.previous(),
+            Command::OnDown => profiles.next(OPTIONS.get().wrap_lists),
+            Command::OnUp => profiles.previous(OPTIONS.get().wrap_lists),
             Command::SelectFirst => profiles.select_first(),
             Command::SelectLast => profiles.select_last(),
             Command::EnterSearch => app.search_new_pattern(),
             Command::RepeatLastSearch => app.repeat_search(),
             Command::RepeatLastSearchBackward => app.repeat_search_reverse(),
+            Command::OpenFuzzyFinder => app.open_fuzzy_finder(false),
             Command::OpenGameWindow => app.open_game_window(),
+            Command::OpenProfileWindow => app.close_selection_window(),
+            Command::ToggleHelp => app.help.open(&app.mode),
             Command::Quit => return true,
             _ => (),
         }
@@ -307,15 +397,17 @@ fn handle_key_profile_selection_mode(key: KeyEvent, app: &mut App) -> bool {
 }
 
 fn handle_key_help_mode(key: KeyEvent, help: &mut Help) -> bool {
-    if let Some(command) = KEY_BINDINGS.help.get(&key) {
+    if let Some(command) = KEY_BINDINGS.get().help.get(&key) {
         match command {
             HelpCommand::ScrollUp => help.scroller.scroll_up(),
             HelpCommand::ScrollDown => help.scroller.scroll_down(),
             HelpCommand::GoToTop => help.scroller.scroll_top(),
             HelpCommand::GoToBottom => help.scroller.scroll_bottom(),
+            HelpCommand::Grow => help.resize(true),
+            HelpCommand::Shrink => help.resize(false),
             HelpCommand::Abort => help.toggle(),
         }
-    } else if let Some(command) = KEY_BINDINGS.get(&key) {
+    } else if let Some(command) = KEY_BINDINGS.get().get(&key) {
         match command {
             Command::OnDown => help.scroller.scroll_down(),
             Command::OnUp => help.scroller.scroll_up(),
@@ -330,12 +422,48 @@ fn handle_key_help_mode(key: KeyEvent, help: &mut Help) -> bool {
     false
 }
 
+fn handle_key_event_log_mode(key: KeyEvent, event_log: &mut EventLog) -> bool {
+    if let Some(command) = KEY_BINDINGS.get().help.get(&key) {
+        match command {
+            HelpCommand::ScrollUp => event_log.scroller.scroll_up(),
+            HelpCommand::ScrollDown => event_log.scroller.scroll_down(),
+            HelpCommand::GoToTop => event_log.scroller.scroll_top(),
+            HelpCommand::GoToBottom => event_log.scroller.scroll_bottom(),
+            HelpCommand::Abort => event_log.toggle(),
+            HelpCommand::Grow | HelpCommand::Shrink => (),
+        }
+    } else if let Some(command) = KEY_BINDINGS.get().get(&key) {
+        match command {
+            Command::OnDown => event_log.scroller.scroll_down(),
+            Command::OnUp => event_log.scroller.scroll_up(),
+            Command::SelectFirst => event_log.scroller.scroll_top(),
+            Command::SelectLast => event_log.scroller.scroll_bottom(),
+            Command::ToggleEventLog => event_log.toggle(),
+            Command::Quit => return true,
+            _ => (),
+        }
+    }
+
+    false
+}
+
 fn handle_key_confirmation_mode(key: KeyEvent, app: &mut App) -> bool {
     let Mode::Confirmation(prompt) = &mut app.mode else {
         unreachable!();
     };
 
-    if let Some(command) = KEY_BINDINGS.confirmation.get(&key) {
+    let name_matches = prompt.name_matches();
+
+    if let Some(input) = &mut prompt.name_input {
+        match key.code {
+            KeyCode::Enter if name_matches => app.on_confirmation(),
+            KeyCode::Enter => app.message.set_warning("The name doesn't match"),
+            KeyCode::Esc => app.mode.select_previous(),
+            _ => {
+                input.update(key);
+            }
+        }
+    } else if let Some(command) = KEY_BINDINGS.get().confirmation.get(&key) {
         match command {
             ConfirmationCommand::Confirm => app.on_confirmation(),
             ConfirmationCommand::Cancel => app.mode.select_previous(),
@@ -344,12 +472,13 @@ fn handle_key_confirmation_mode(key: KeyEvent, app: &mut App) -> bool {
             ConfirmationCommand::GoToTop => prompt.scroller.scroll_top(),
             ConfirmationCommand::GoToBottom => prompt.scroller.scroll_bottom(),
         }
-    } else if let Some(command) = KEY_BINDINGS.get(&key) {
+    } else if let Some(command) = KEY_BINDINGS.get().get(&key) {
         match command {
             Command::OnDown => prompt.scroller.scroll_down(),
             Command::OnUp => prompt.scroller.scroll_up(),
             Command::SelectFirst => prompt.scroller.scroll_top(),
             Command::SelectLast => prompt.scroller.scroll_bottom(),
+            Command::ToggleHelp => app.help.open(&app.mode),
             Command::Quit => return true,
             _ => (),
         }
@@ -362,11 +491,13 @@ pub fn handle_key_fuzzy_mode(key: KeyEvent, fuzzy_finder: &mut FuzzyFinder) {
     let input = &mut fuzzy_finder.input;
 
     match (key.code, key.modifiers) {
+        (KeyCode::Up, KeyModifiers::ALT) => fuzzy_finder.resize(true),
+        (KeyCode::Down, KeyModifiers::ALT) => fuzzy_finder.resize(false),
         (KeyCode::Down | KeyCode::Tab, _) | (KeyCode::Char('n'), KeyModifiers::CONTROL) => {
-            fuzzy_finder.matched.next();
+            fuzzy_finder.matched.next(true);
         }
         (KeyCode::Up | KeyCode::BackTab, _) | (KeyCode::Char('p'), KeyModifiers::CONTROL) => {
-            fuzzy_finder.matched.previous();
+            fuzzy_finder.matched.previous(true);
         }
         _ if input.update(key) => fuzzy_finder.update_matches(),
         _ => {}
@@ -383,9 +514,15 @@ fn handle_key_editing_mode(key: KeyEvent, app: &mut App) {
             } else if let Some(input) = &mut app.footer_input {
                 let changed = input.update(key);
 
-                if changed && matches!(app.mode, Mode::Search(_)) && OPTIONS.incremental_search {
+                if changed
+                    && let Mode::Search(context) = app.mode
+                    && OPTIONS.get().incremental_search.enabled_in(context)
+                {
                     app.search.pattern = app.footer_input.as_ref().unwrap().text.clone();
                     app.run_search(Direction::Forward);
+                } else if changed && matches!(app.mode, Mode::Filter) {
+                    app.filter.pattern = app.footer_input.as_ref().unwrap().text.clone();
+                    app.apply_filter();
                 }
             }
         }
@@ -396,11 +533,12 @@ fn complete(app: &mut App) {
     let res = match &app.mode {
         Mode::EntryRenaming => app.rename_selected_entry(),
         Mode::FolderCreation(top_level) => app.create_folder(*top_level),
+        Mode::BulkRenaming => app.prepare_bulk_rename(),
         Mode::GameCreation => {
             if app.game_creation.edit {
-                let savefile_path = &app.extract_input();
+                let savefile_paths = utils::split_paths(&app.extract_input());
                 let game = app.games.inner.get_selected_mut().unwrap();
-                game.set_savefile_path(savefile_path)
+                game.set_savefile_paths(savefile_paths)
             } else {
                 app.handle_game_creation()
             }
@@ -419,8 +557,20 @@ fn complete(app: &mut App) {
                 .get_game_unchecked_mut()
                 .rename_selected_profile(&new_name)
         }
+        Mode::ProfileNoteEditing => {
+            let note = app.extract_input();
+            app.games
+                .get_game_unchecked_mut()
+                .set_selected_profile_note(&note)
+        }
         Mode::Search(..) => app.complete_search(),
-        Mode::Normal
+        Mode::Filter => {
+            app.filter.pattern = app.extract_input();
+            app.apply_filter();
+            Ok(())
+        }
+        Mode::MatchExport => app.export_matches(),
+        Mode::Normal | Mode::GameSelection | Mode::ProfileSelection
             if app.fuzzy_finder.is_active() && !app.fuzzy_finder.matched.items.is_empty() =>
         {
             app.jump_to_entry();
@@ -435,24 +585,8 @@ fn complete(app: &mut App) {
 
 fn abort(app: &mut App) {
     match &mut app.mode {
-        Mode::GameSelection => {
-            if app.games.get_game().is_none() {
-                app.message
-                    .set_warning("Can't abort while no game is selected");
-            } else if app.games.get_profile().is_none() {
-                app.open_profile_window();
-            } else {
-                app.mode.select_previous();
-            }
-        }
-        Mode::ProfileSelection => {
-            if app.games.get_profile().is_some() {
-                app.mode.select_previous();
-            } else {
-                app.message
-                    .set_warning("Can't abort while no profile is selected");
-            }
-        }
+        _ if app.fuzzy_finder.is_active() => app.fuzzy_finder.reset(),
+        Mode::GameSelection | Mode::ProfileSelection => app.close_selection_window(),
         Mode::GameCreation => match app.game_creation.step {
             Step::EnterName => app.abort_input(),
             Step::EnterPath => {
@@ -462,11 +596,18 @@ fn abort(app: &mut App) {
             _ => unreachable!(),
         },
         Mode::EntryRenaming
+        | Mode::BulkRenaming
         | Mode::FolderCreation(..)
         | Mode::ProfileCreation
         | Mode::ProfileRenaming
-        | Mode::GameRenaming => app.abort_input(),
+        | Mode::ProfileNoteEditing
+        | Mode::GameRenaming
+        | Mode::MatchExport => app.abort_input(),
         Mode::Search(_) => app.abort_search(),
+        Mode::Filter => {
+            app.abort_input();
+            app.clear_filter();
+        }
         Mode::Normal => app.fuzzy_finder.reset(),
         Mode::Confirmation(_) => (),
     }
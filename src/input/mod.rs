@@ -23,6 +23,8 @@ pub struct Input {
     pub cursor_offset: u16,
     available_width: usize,
     change: Option<InputChange>,
+    /// Whether the whole text is selected, in which case typing replaces it.
+    pub selected: bool,
 }
 
 impl Default for Input {
@@ -36,6 +38,7 @@ impl Default for Input {
             cursor_offset: 0,
             available_width: usize::MAX,
             change: None,
+            selected: false,
         }
     }
 }
@@ -186,6 +189,20 @@ impl Input {
     fn update(&mut self, key: KeyEvent) -> bool {
         self.change = None;
 
+        if std::mem::take(&mut self.selected)
+            && matches!(
+                (key.code, key.modifiers),
+                (KeyCode::Backspace, _)
+                    | (KeyCode::Char(_), KeyModifiers::NONE | KeyModifiers::SHIFT)
+            )
+        {
+            self.clear_line();
+
+            if key.code == KeyCode::Backspace {
+                return self.change.is_some();
+            }
+        }
+
         match (key.code, key.modifiers) {
             (KeyCode::Left, KeyModifiers::CONTROL) => self.move_cursor_one_word_left(),
             (KeyCode::Right, KeyModifiers::CONTROL) => self.move_cursor_one_word_right(),
@@ -250,8 +267,12 @@ impl From<&Mode> for Input {
             Mode::Search(_) => "/",
             Mode::GameCreation => "Game Name: ",
             Mode::ProfileCreation => "Profile Name: ",
+            Mode::ProfileNoteEditing => "Note: ",
             Mode::EntryRenaming | Mode::ProfileRenaming | Mode::GameRenaming => "Rename: ",
             Mode::FolderCreation(_) => "Folder Name: ",
+            Mode::BulkRenaming => "Template: ",
+            Mode::Filter => "Filter: ",
+            Mode::MatchExport => "Export to: ",
             Mode::Normal => "",
             _ => panic!(),
         };
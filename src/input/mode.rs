@@ -9,21 +9,31 @@ pub enum Mode {
     Normal,
     Confirmation(Prompt),
     EntryRenaming,
+    BulkRenaming,
     GameSelection,
     GameCreation,
     GameRenaming,
     ProfileSelection,
     ProfileCreation,
     ProfileRenaming,
+    ProfileNoteEditing,
     FolderCreation(bool),
     Search(SearchContext),
+    Filter,
+    MatchExport,
 }
 
 impl Mode {
     pub fn select_previous(&mut self) {
         *self = match self {
             Mode::Confirmation(prompt) => match prompt.context {
-                ConfirmationContext::Deletion | ConfirmationContext::Replacing => Mode::Normal,
+                ConfirmationContext::Deletion
+                | ConfirmationContext::Replacing
+                | ConfirmationContext::ReplacingWithActive
+                | ConfirmationContext::BulkRename
+                | ConfirmationContext::Pruning
+                | ConfirmationContext::Move { .. }
+                | ConfirmationContext::ImportOverwrite => Mode::Normal,
                 ConfirmationContext::GameDeletion => Mode::GameSelection,
                 ConfirmationContext::ProfileDeletion => Mode::ProfileSelection,
             },
@@ -34,11 +44,16 @@ impl Mode {
                 SearchContext::ProfileSelection => Mode::ProfileSelection,
             },
             Mode::EntryRenaming
+            | Mode::BulkRenaming
             | Mode::FolderCreation(_)
+            | Mode::Filter
+            | Mode::MatchExport
             | Mode::GameSelection
             | Mode::ProfileSelection => Mode::Normal,
             Mode::GameCreation | Mode::GameRenaming => Mode::GameSelection,
-            Mode::ProfileCreation | Mode::ProfileRenaming => Mode::ProfileSelection,
+            Mode::ProfileCreation | Mode::ProfileRenaming | Mode::ProfileNoteEditing => {
+                Mode::ProfileSelection
+            }
             Mode::Normal => unreachable!(),
         };
     }
@@ -67,6 +82,7 @@ impl Mode {
             Mode::ProfileSelection
                 | Mode::ProfileCreation
                 | Mode::ProfileRenaming
+                | Mode::ProfileNoteEditing
                 | Mode::Search(SearchContext::ProfileSelection)
         )
     }
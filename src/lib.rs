@@ -9,8 +9,11 @@ mod commands;
 pub mod config;
 mod entry;
 mod event;
+pub mod event_log;
+pub mod filter;
 mod fuzzy_finder;
 mod game;
+mod hash_cache;
 mod help;
 mod input;
 mod message;
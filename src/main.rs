@@ -5,6 +5,10 @@ use clap_complete::CompleteEnv;
 async fn main() -> anyhow::Result<()> {
     CompleteEnv::with_factory(cli::build_command).complete();
 
+    if cli::handle_doctor_subcommand() || cli::handle_keys_subcommand() {
+        return Ok(());
+    }
+
     let mut app = app::App::new()?;
 
     if cli::handle_subcommands(&mut app) {
@@ -1,4 +1,4 @@
-use crate::event::Event;
+use crate::{config::OPTIONS, event::Event};
 use anyhow::Error;
 use std::ops::Deref;
 use tokio::sync::mpsc::UnboundedSender;
@@ -25,6 +25,8 @@ pub struct Message {
     pub kind: Kind,
     token: CancellationToken,
     tx: UnboundedSender<Event>,
+    /// Whether the terminal bell should be rung before the next draw.
+    bell: bool,
 }
 
 impl Message {
@@ -34,40 +36,58 @@ impl Message {
             kind: Kind::Info,
             token: CancellationToken::new(),
             tx,
+            bell: false,
         }
     }
 
-    pub fn set_info(&mut self, message: &str) {
+    fn set(&mut self, message: &str, kind: Kind) {
         self.token.cancel();
 
         if !message.is_empty() {
+            if let Kind::Error = kind {
+                self.ring_bell();
+            }
+
             message.clone_into(&mut self.message);
-            self.kind = Kind::Info;
+            self.kind = kind;
             self.token = CancellationToken::new();
         }
     }
 
+    /// Requests the terminal bell to be rung if the `bell_on_complete` option is set.
+    pub fn ring_bell(&mut self) {
+        self.bell |= OPTIONS.get().bell_on_complete;
+    }
+
+    /// Returns whether the terminal bell was requested since the last call.
+    pub fn take_bell(&mut self) -> bool {
+        std::mem::take(&mut self.bell)
+    }
+
+    /// Sets an info message that is cleared after `message_timeout` seconds.
+    pub fn set_info(&mut self, message: &str) {
+        self.set(message, Kind::Info);
+        self.clear_timeout(OPTIONS.get().message_timeout);
+    }
+
     pub fn set_message_with_timeout(&mut self, message: &str, duration: u64) {
-        self.set_info(message);
+        self.set(message, Kind::Info);
         self.clear_timeout(duration);
     }
 
+    /// Sets an error message that stays until it is replaced or cleared.
     pub fn set_error(&mut self, error: &Error) {
-        self.set_info(&error.to_string());
-        self.kind = Kind::Error;
-        self.clear_timeout(10);
+        self.set(&error.to_string(), Kind::Error);
     }
 
     pub fn set_error_from_str(&mut self, message: &str) {
-        self.set_info(message);
-        self.kind = Kind::Error;
-        self.clear_timeout(10);
+        self.set(message, Kind::Error);
     }
 
+    /// Sets a warning message that is cleared after `message_timeout` seconds.
     pub fn set_warning(&mut self, message: &str) {
-        self.set_info(message);
-        self.kind = Kind::Warning;
-        self.clear_timeout(10);
+        self.set(message, Kind::Warning);
+        self.clear_timeout(OPTIONS.get().message_timeout);
     }
 
     pub fn clear(&mut self) {
@@ -75,7 +95,13 @@ impl Message {
         self.token.cancel();
     }
 
+    /// Schedules clearing the message after `duration` seconds. A duration of 0 keeps the
+    /// message until it is replaced or cleared.
     fn clear_timeout(&mut self, duration: u64) {
+        if duration == 0 || self.message.is_empty() {
+            return;
+        }
+
         let token = self.token.clone();
         let tx = self.tx.clone();
 
@@ -25,6 +25,8 @@ pub struct Search {
     pub matches: Vec<usize>,
     pub pattern: String,
     pub start_idx: Option<usize>,
+    /// Position of the last jumped match in `matches`.
+    pub current: Option<usize>,
 }
 
 impl Search {
@@ -56,6 +58,21 @@ impl Search {
     /// assert_eq!(idx, Some(1));
     /// ```
     pub fn search(&mut self, list: &[Utf32String], direction: Direction) -> Option<usize> {
+        self.matches = self.find_matches(list);
+
+        let idx = match direction {
+            Direction::Forward => self.next_match(),
+            Direction::Backward => self.previous_match(),
+        }
+        .copied();
+
+        self.current = idx.and_then(|idx| self.matches.iter().position(|i| *i == idx));
+
+        idx
+    }
+
+    /// Returns the indices of the items in the list that match the pattern.
+    pub fn find_matches(&mut self, list: &[Utf32String]) -> Vec<usize> {
         let pattern = Pattern::new(
             &self.pattern,
             CaseMatching::Smart,
@@ -63,18 +80,35 @@ impl Search {
             AtomKind::Substring,
         );
 
-        self.matches = list
-            .iter()
+        list.iter()
             .enumerate()
             .filter(|(_, s)| pattern.score(s.slice(..), &mut self.matcher).is_some())
             .map(|(idx, _)| idx)
-            .collect();
+            .collect()
+    }
 
-        match direction {
-            Direction::Forward => self.next_match(),
-            Direction::Backward => self.previous_match(),
-        }
-        .copied()
+    /// Returns the position of the current match starting from 1 and the number of matches.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bawa::search::{Search, Direction};
+    /// # use nucleo_matcher::Utf32String;
+    ///
+    /// let mut search = Search::default();
+    /// search.pattern = String::from("ap");
+    ///
+    /// let list = ["apple", "pineapple", "clementine"]
+    ///     .iter()
+    ///     .map(|s| Utf32String::from(*s))
+    ///     .collect::<Vec<_>>();
+    ///
+    /// search.search(&list, Direction::Backward);
+    /// assert_eq!(search.counter(), Some((2, 2)));
+    /// ```
+    pub fn counter(&self) -> Option<(usize, usize)> {
+        self.current
+            .map(|current| (current + 1, self.matches.len()))
     }
 
     /// Returns the index of the first match after `start_idx` or the first match if it is `None`.
@@ -98,9 +132,38 @@ impl Search {
 }
 
 impl App {
+    fn reveals_search_matches(&self) -> bool {
+        OPTIONS.get().reveal_search_matches && !self.flat_view
+    }
+
+    /// Returns the ids of the entries that are searched, in the order they are displayed. The
+    /// entries inside collapsed folders are included if the `reveal_search_matches` option is set.
+    fn searchable_ids(&self) -> Vec<NodeId> {
+        match self.games.get_entries() {
+            Some(entries) if self.reveals_search_matches() => {
+                entries.descendants(NodeId::root()).skip(1).collect()
+            }
+            _ => self.visible_ids(),
+        }
+    }
+
+    /// Returns the ids of the searched entries that match the search pattern.
+    pub fn search_match_ids(&mut self) -> Vec<NodeId> {
+        let ids = self.searchable_ids();
+        let entries = self.games.get_entries().unwrap();
+        let items = (ids.iter())
+            .map(|id| Utf32String::from(entries[*id].to_string()))
+            .collect::<Vec<_>>();
+
+        (self.search.find_matches(&items).into_iter())
+            .map(|idx| ids[idx])
+            .collect()
+    }
+
     pub fn search_new_pattern(&mut self) {
         self.take_input(Mode::Search(self.mode.search_context()));
         self.search.start_idx = self.get_search_start_position();
+        self.search.current = None;
     }
 
     pub fn run_search(&mut self, direction: Direction) {
@@ -112,8 +175,8 @@ impl App {
         let items: Vec<_> = match self.mode.search_context() {
             Context::Normal => {
                 let entries = self.games.get_entries().unwrap();
-                entries
-                    .visible(NodeId::root())
+                self.searchable_ids()
+                    .into_iter()
                     .map(|id| Utf32String::from(entries[id].to_string()))
                     .collect()
             }
@@ -150,6 +213,7 @@ impl App {
             self.message.clear();
             self.jump_to_match(Some(idx));
         } else {
+            self.search.current = None;
             self.jump_to_match(self.search.start_idx);
             self.message
                 .set_error_from_str(&format!("Pattern not found: {}", self.search.pattern));
@@ -157,9 +221,10 @@ impl App {
     }
 
     pub fn complete_search(&mut self) -> Result<()> {
+        let context = self.mode.search_context();
         self.search.pattern = self.extract_input();
 
-        if !OPTIONS.incremental_search {
+        if !OPTIONS.get().incremental_search.enabled_in(context) {
             self.run_search(Direction::Forward);
         }
 
@@ -184,22 +249,57 @@ impl App {
     pub fn repeat_search(&mut self) {
         self.search.start_idx = self.get_search_start_position();
         self.run_search(Direction::Forward);
+        self.show_search_counter();
     }
 
     pub fn repeat_search_reverse(&mut self) {
         self.search.start_idx = self.get_search_start_position();
         self.run_search(Direction::Backward);
+        self.show_search_counter();
+    }
+
+    /// Marks the entries that match the last search pattern. The entries inside collapsed folders
+    /// are included if the `reveal_search_matches` option is set.
+    pub fn mark_search_matches(&mut self) {
+        if self.search.pattern.is_empty() {
+            self.message.set_warning("There is no search pattern");
+            return;
+        }
+
+        let Some(entries) = self.games.get_entries() else {
+            return;
+        };
+
+        let ids = self.searchable_ids();
+        let items = (ids.iter())
+            .map(|id| Utf32String::from(entries[*id].to_string()))
+            .collect::<Vec<_>>();
+
+        let count = (self.search.find_matches(&items).into_iter())
+            .filter(|idx| self.tree_state.mark(ids[*idx]))
+            .count();
+
+        self.message.set_info(&format!(
+            "Marked {count} entries matching: {}",
+            self.search.pattern
+        ));
+    }
+
+    fn show_search_counter(&mut self) {
+        if let Some((current, total)) = self.search.counter() {
+            self.message.set_info(&format!(
+                "Match {current} of {total}: {}",
+                self.search.pattern
+            ));
+        }
     }
 
     fn get_search_start_position(&mut self) -> Option<usize> {
         match self.mode.search_context() {
-            Context::Normal => self.tree_state.selected.and_then(|selected| {
-                self.games
-                    .get_entries()
-                    .unwrap()
-                    .visible(NodeId::root())
-                    .position(|id| id == selected)
-            }),
+            Context::Normal => self
+                .tree_state
+                .selected
+                .and_then(|selected| (self.searchable_ids().iter()).position(|id| *id == selected)),
             Context::ProfileSelection => self.games.get_profiles().state.selected(),
             Context::GameSelection => self.games.inner.state.selected(),
             Context::GameCreation => match &self.game_creation.step {
@@ -215,8 +315,15 @@ impl App {
         if let Some(idx) = idx {
             match self.mode.search_context() {
                 Context::Normal => {
-                    let mut visible = self.games.get_entries().unwrap().visible(NodeId::root());
-                    self.tree_state.select_unchecked(visible.nth(idx));
+                    let id = self.searchable_ids().get(idx).copied();
+
+                    if self.reveals_search_matches() {
+                        let entries = self.games.get_entries_mut().unwrap();
+                        self.tree_state.select(id, entries);
+                    } else {
+                        self.tree_state.select_unchecked(id);
+                    }
+
                     self.auto_mark_save_file();
                 }
                 Context::ProfileSelection => self.games.get_profiles_mut().state.select(Some(idx)),
@@ -0,0 +1,136 @@
+//! A representation of the tree that doesn't depend on the ids of the nodes, for storing it.
+//!
+//! The nodes are listed in depth-first order and refer to their parents by their position in the
+//! list, so the representation stays the same regardless of the order the nodes were added to the
+//! tree. The profile state files store their entries as nested names instead and don't need a
+//! migration. A state format based on this representation would read the current one as a
+//! fallback, like `ProfileState::from_bytes` does for the files written before pinned entries.
+
+use super::{Node, NodeId, Tree};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct FlatNode<T> {
+    /// Position of the parent in the list, `None` for the root.
+    pub parent: Option<usize>,
+    pub expanded: Option<bool>,
+    pub value: T,
+}
+
+impl<T> Tree<T> {
+    /// Returns the nodes connected to the root in depth-first order. Detached nodes are left out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bawa::tree::{Tree, flat::FlatNode};
+    /// let mut tree = Tree::default();
+    /// let r = tree.add_value("r");
+    /// let b = tree.add_value("b");
+    /// let a = tree.add_value("a");
+    /// let a_c = tree.add_value("c");
+    /// tree.append(r, a);
+    /// tree.append(a, a_c);
+    /// tree.append(r, b);
+    ///
+    /// let flat = tree.to_flat();
+    /// let parents = flat.iter().map(|node| node.parent).collect::<Vec<_>>();
+    /// let values = flat.iter().map(|node| *node.value).collect::<Vec<_>>();
+    ///
+    /// assert_eq!(parents, [None, Some(0), Some(1), Some(0)]);
+    /// assert_eq!(values, ["r", "a", "c", "b"]);
+    ///
+    /// let flat = flat.into_iter().map(|node| FlatNode {
+    ///     parent: node.parent,
+    ///     expanded: node.expanded,
+    ///     value: *node.value,
+    /// });
+    /// let copy = Tree::from_flat(flat).unwrap();
+    /// assert_eq!(copy.to_flat(), tree.to_flat());
+    /// ```
+    pub fn to_flat(&self) -> Vec<FlatNode<&T>> {
+        if self.root().is_none() {
+            return Vec::new();
+        }
+
+        let positions = (self.descendants(NodeId::root()).enumerate())
+            .map(|(idx, id)| (id, idx))
+            .collect::<HashMap<NodeId, usize>>();
+
+        self.descendants(NodeId::root())
+            .map(|id| {
+                let node = &self[id];
+
+                FlatNode {
+                    parent: node.parent.map(|parent| positions[&parent]),
+                    expanded: node.expanded,
+                    value: &node.value,
+                }
+            })
+            .collect()
+    }
+
+    /// Builds a tree from the nodes returned by [`Tree::to_flat`]. Returns `None` if a node other
+    /// than the first one doesn't come after its parent.
+    pub fn from_flat(nodes: impl IntoIterator<Item = FlatNode<T>>) -> Option<Self> {
+        let mut tree = Self::default();
+
+        for (idx, flat_node) in nodes.into_iter().enumerate() {
+            let parent = match flat_node.parent {
+                Some(parent) if parent < idx => Some(NodeId::new(parent)),
+                None if idx == 0 => None,
+                _ => return None,
+            };
+
+            let mut node = Node::new(flat_node.value);
+            node.expanded = flat_node.expanded;
+            let id = tree.add_node(node);
+
+            if let Some(parent) = parent {
+                tree.append(parent, id);
+            }
+        }
+
+        Some(tree)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FlatNode;
+    use crate::tree::Tree;
+
+    #[test]
+    fn round_trip() {
+        let mut tree = Tree::default();
+        let r = tree.add_value(String::from("r"));
+        let b = tree.add_value(String::from("b"));
+        let a = tree.add_value(String::from("a"));
+        let a_c = tree.add_value(String::from("c"));
+        tree.append(r, a);
+        tree.append(a, a_c);
+        tree.append(r, b);
+        tree[a].expanded = Some(true);
+
+        let bytes = bincode::serialize(&tree.to_flat()).unwrap();
+        let flat = bincode::deserialize::<Vec<FlatNode<String>>>(&bytes).unwrap();
+        let copy = Tree::from_flat(flat).unwrap();
+
+        assert_eq!(copy.to_flat(), tree.to_flat());
+
+        let invalid = [
+            FlatNode {
+                parent: None,
+                expanded: None,
+                value: 0,
+            },
+            FlatNode {
+                parent: Some(1),
+                expanded: None,
+                value: 1,
+            },
+        ];
+        assert!(Tree::from_flat(invalid).is_none());
+    }
+}
@@ -1,11 +1,15 @@
 pub use id::NodeId;
 pub use node::Node;
-use std::ops::{Index, IndexMut};
+use std::{
+    collections::{HashMap, HashSet},
+    ops::{Index, IndexMut},
+};
 use traverse::{
     Ancestors, Children, Descendants, FollowingSiblings, PrecedingSiblings, Predecessors, Visible,
 };
 pub use widget::TreeState;
 
+pub mod flat;
 mod id;
 mod node;
 mod relations;
@@ -302,6 +306,44 @@ impl<T> Tree<T> {
         f(self, relative, entry);
     }
 
+    /// Moves the children of [from](NodeId) after the children of [to](NodeId), keeping their
+    /// order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bawa::tree::Tree;
+    /// let mut tree = Tree::default();
+    ///
+    /// let r = tree.add_value("r");
+    /// let a = tree.add_value("a");
+    /// let b = tree.add_value("b");
+    /// let a_c = tree.add_value("c");
+    /// let a_d = tree.add_value("d");
+    /// let b_e = tree.add_value("e");
+    ///
+    /// tree.append(r, a);
+    /// tree.append(r, b);
+    /// tree.append(a, a_c);
+    /// tree.append(a, a_d);
+    /// tree.append(b, b_e);
+    ///
+    /// tree.move_children(a, b);
+    ///
+    /// assert!(tree[a].first_child().is_none());
+    ///
+    /// let mut iter = tree.children(b);
+    /// assert_eq!(iter.next(), Some(b_e));
+    /// assert_eq!(iter.next(), Some(a_c));
+    /// assert_eq!(iter.next(), Some(a_d));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    pub fn move_children(&mut self, from: NodeId, to: NodeId) {
+        while let Some(child) = self[from].first_child {
+            self.move_entry(Tree::append, to, child);
+        }
+    }
+
     /// Returns `true` if the node is not connected to the root node.
     ///
     /// # Examples
@@ -323,6 +365,44 @@ impl<T> Tree<T> {
             .is_some_and(|id| id != NodeId::root())
     }
 
+    /// Returns the deepest node that is an ancestor of both nodes, where a node counts as its own
+    /// ancestor. Returns `None` if the nodes are in unrelated subtrees.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bawa::tree::Tree;
+    /// let mut tree = Tree::default();
+    /// let r = tree.add_value("r");
+    /// let a = tree.add_value("a");
+    /// let b = tree.add_value("b");
+    /// let a_c = tree.add_value("c");
+    /// let a_d = tree.add_value("d");
+    /// let a_d_e = tree.add_value("e");
+    /// let f = tree.add_value("f");
+    /// let f_g = tree.add_value("g");
+    /// tree.append(r, a);
+    /// tree.append(a, a_c);
+    /// tree.append(a, a_d);
+    /// tree.append(a_d, a_d_e);
+    /// tree.append(r, b);
+    /// tree.append(f, f_g);
+    ///
+    /// // siblings
+    /// assert_eq!(tree.lowest_common_ancestor(a_c, a_d), Some(a));
+    /// assert_eq!(tree.lowest_common_ancestor(a_d_e, b), Some(r));
+    /// // ancestor and descendant
+    /// assert_eq!(tree.lowest_common_ancestor(a, a_d_e), Some(a));
+    /// assert_eq!(tree.lowest_common_ancestor(a_d_e, a_d_e), Some(a_d_e));
+    /// // unrelated subtrees
+    /// assert_eq!(tree.lowest_common_ancestor(a_c, f_g), None);
+    /// ```
+    pub fn lowest_common_ancestor(&self, a: NodeId, b: NodeId) -> Option<NodeId> {
+        let a_ancestors = Ancestors::new(a, self).collect::<HashSet<NodeId>>();
+
+        Ancestors::new(b, self).find(|id| a_ancestors.contains(id))
+    }
+
     /// Returns an iterator over the ids of the nodes.
     ///
     /// # Examples
@@ -439,6 +519,53 @@ impl<T> Tree<T> {
         skip_first!(Ancestors::new(node, self))
     }
 
+    /// Returns the number of edges between the node and the root of its tree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bawa::tree::Tree;
+    /// let mut tree = Tree::default();
+    /// let r = tree.add_value("r");
+    /// let a = tree.add_value("a");
+    /// let a_b = tree.add_value("b");
+    /// tree.append(r, a);
+    /// tree.append(a, a_b);
+    ///
+    /// assert_eq!(tree.depth_of(r), 0);
+    /// assert_eq!(tree.depth_of(a), 1);
+    /// assert_eq!(tree.depth_of(a_b), 2);
+    /// ```
+    pub fn depth_of(&self, id: NodeId) -> usize {
+        self.ancestors(id).count()
+    }
+
+    /// Returns the number of edges on the longest path from the node to a leaf descendant.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bawa::tree::Tree;
+    /// let mut tree = Tree::default();
+    /// let r = tree.add_value("r");
+    /// let a = tree.add_value("a");
+    /// let b = tree.add_value("b");
+    /// let b_c = tree.add_value("c");
+    /// tree.append(r, a);
+    /// tree.append(r, b);
+    /// tree.append(b, b_c);
+    ///
+    /// assert_eq!(tree.height(r), 2);
+    /// assert_eq!(tree.height(b), 1);
+    /// assert_eq!(tree.height(a), 0);
+    /// ```
+    pub fn height(&self, id: NodeId) -> usize {
+        self.children(id)
+            .map(|child| self.height(child) + 1)
+            .max()
+            .unwrap_or(0)
+    }
+
     /// Returns an iterator over the given node's predecessors.
     ///
     /// # Examples
@@ -589,6 +716,65 @@ impl<T> Tree<T> {
     }
 }
 
+impl<T: Clone> Tree<T> {
+    /// Adds a copy of the subtree rooted at the given node to the tree and returns the id of the
+    /// copy's root. The copy is detached and keeps the fold states of the original.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bawa::tree::{Tree, NodeId};
+    /// let mut tree = Tree::default();
+    /// let r = tree.add_value("r");
+    /// let a = tree.add_value("a");
+    /// let a_b = tree.add_value("b");
+    /// let a_c = tree.add_value("c");
+    /// let a_c_d = tree.add_value("d");
+    /// tree.append(r, a);
+    /// tree.append(a, a_b);
+    /// tree.append(a, a_c);
+    /// tree.append(a_c, a_c_d);
+    ///
+    /// let copy = tree.subtree_clone(a);
+    /// assert!(tree[copy].parent().is_none());
+    ///
+    /// fn values<'a>(tree: &Tree<&'a str>, id: NodeId) -> Vec<&'a str> {
+    ///     tree.descendants(id).map(|id| *tree[id]).collect()
+    /// }
+    ///
+    /// assert_eq!(values(&tree, copy), ["a", "b", "c", "d"]);
+    ///
+    /// let copy_c = tree.children(copy).last().unwrap();
+    /// assert_eq!(tree.children(copy_c).map(|id| *tree[id]).collect::<Vec<_>>(), ["d"]);
+    ///
+    /// // the original is independent of the copy
+    /// *tree[copy_c] = "e";
+    /// tree.detach(copy_c);
+    /// assert_eq!(values(&tree, a), ["a", "b", "c", "d"]);
+    /// assert_eq!(values(&tree, copy), ["a", "b"]);
+    /// ```
+    pub fn subtree_clone(&mut self, id: NodeId) -> NodeId {
+        let ids = self.descendants(id).collect::<Vec<NodeId>>();
+        let mut copies = HashMap::with_capacity(ids.len());
+
+        for old_id in ids {
+            let old = &self[old_id];
+            let parent = old.parent;
+            let mut node = Node::new(old.value.clone());
+            node.expanded = old.expanded;
+
+            let new_id = self.add_node(node);
+            copies.insert(old_id, new_id);
+
+            if old_id != id {
+                self.append(copies[&parent.unwrap()], new_id);
+            }
+        }
+
+        copies[&id]
+    }
+}
+
 impl<T> Default for Tree<T> {
     fn default() -> Self {
         Self {
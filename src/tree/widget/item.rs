@@ -1,5 +1,5 @@
 use crate::{
-    config::OPTIONS,
+    config::{OPTIONS, options::PathDisplay},
     tree::{NodeId, Tree},
 };
 use ratatui::{
@@ -19,6 +19,8 @@ impl TreeItem<'_> {
     where
         T: Display,
     {
+        let options = OPTIONS.get();
+
         let indent_guides = if last_item {
             format!("{}  └ ", "  │ ".repeat(depth - 1))
         } else {
@@ -27,19 +29,40 @@ impl TreeItem<'_> {
         let folder = match tree[id].expanded {
             Some(true) => format!(
                 "{} {} ",
-                OPTIONS.icons.arrow_open, OPTIONS.icons.folder_open
+                options.icons.arrow_open, options.icons.folder_open
             ),
             Some(false) => format!(
                 "{} {} ",
-                OPTIONS.icons.arrow_closed, OPTIONS.icons.folder_closed
+                options.icons.arrow_closed, options.icons.folder_closed
             ),
             None => String::from(" "),
         };
 
+        let mut name = match options.path_display {
+            PathDisplay::RelativePath if id != NodeId::root() => {
+                let mut names = std::iter::once(id)
+                    .chain(tree.ancestors(id))
+                    .filter(|id| *id != NodeId::root())
+                    .map(|id| tree[id].value.to_string())
+                    .collect::<Vec<_>>();
+                names.reverse();
+                names.join("/")
+            }
+            _ => tree[id].to_string(),
+        };
+
+        if options.show_counts && tree[id].expanded == Some(false) {
+            // only files have no fold state
+            let count = (tree.descendants(id))
+                .filter(|id| tree[*id].expanded.is_none())
+                .count();
+            name = format!("{name} ({count})");
+        }
+
         let line = Line::from(vec![
             Span::styled(indent_guides, Color::DarkGray),
             Span::raw(folder),
-            Span::raw(tree[id].to_string()),
+            Span::raw(name),
         ]);
 
         Self {
@@ -48,4 +71,17 @@ impl TreeItem<'_> {
             id,
         }
     }
+
+    /// Prepends `prefix` to the name, which is the last span of the line. Does nothing if the
+    /// relative paths are shown, as they already contain it.
+    pub fn prefix_name(mut self, prefix: &str) -> Self {
+        if OPTIONS.get().path_display == PathDisplay::Name
+            && let Some(name) =
+                (self.content.lines.first_mut()).and_then(|line| line.spans.last_mut())
+        {
+            name.content = format!("{prefix}{}", name.content).into();
+        }
+
+        self
+    }
 }
@@ -1,22 +1,23 @@
-use super::{state::TreeState, tree::Tree};
-use crate::tree::NodeId;
+use super::{item::TreeItem, state::TreeState, tree::Tree};
+use crate::{config::options::LineNumbers, tree::NodeId};
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
     prelude::BlockExt,
+    style::Color,
     text::Span,
-    widgets::{StatefulWidget, Widget},
+    widgets::{Scrollbar, ScrollbarOrientation, ScrollbarState, StatefulWidget, Widget},
 };
 
 impl StatefulWidget for Tree<'_> {
     type State = TreeState;
 
-    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+    fn render(mut self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
         buf.set_style(area, self.style);
         if let Some(block) = &self.block {
             block.render(area, buf);
         }
-        let tree_area = self.block.inner_if_some(area);
+        let mut tree_area = self.block.inner_if_some(area);
 
         if tree_area.is_empty() || self.items.is_empty() {
             return;
@@ -29,8 +30,25 @@ impl StatefulWidget for Tree<'_> {
 
         state.offset = first_visible_idx;
 
-        for (i, mut item) in self
-            .items
+        if self.scrollbar && self.items.len() > tree_height {
+            let mut scrollbar_state = ScrollbarState::new(self.items.len() - tree_height + 1)
+                .position(state.offset)
+                .viewport_content_length(tree_height);
+            Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                .begin_symbol(None)
+                .end_symbol(None)
+                .render(tree_area, buf, &mut scrollbar_state);
+            tree_area.width = tree_area.width.saturating_sub(1);
+        }
+
+        let selected_idx = (self.items.iter()).position(|item| Some(item.id) == state.selected);
+        // the line numbers are right aligned and followed by a space
+        let number_width = match self.line_numbers {
+            LineNumbers::None => 0,
+            _ => self.items.len().to_string().len() as u16 + 1,
+        };
+
+        for (i, mut item) in std::mem::take(&mut self.items)
             .into_iter()
             .skip(state.offset)
             .take(last_visible_idx - first_visible_idx + 1)
@@ -38,18 +56,50 @@ impl StatefulWidget for Tree<'_> {
         {
             let (x, y) = (tree_area.left(), tree_area.top() + i as u16);
 
-            let row_area = Rect {
+            let mut row_area = Rect {
                 x,
                 y,
                 width: tree_area.width,
                 height: 1,
             };
 
+            if number_width != 0 {
+                let idx = state.offset + i;
+                let number = match selected_idx {
+                    Some(selected_idx)
+                        if self.line_numbers == LineNumbers::Relative && selected_idx != idx =>
+                    {
+                        selected_idx.abs_diff(idx)
+                    }
+                    _ => idx + 1,
+                };
+                let width = (number_width - 1) as usize;
+
+                buf.set_span(
+                    x,
+                    y,
+                    &Span::styled(format!("{number:>width$} "), Color::DarkGray),
+                    row_area.width,
+                );
+                row_area.x += number_width.min(row_area.width);
+                row_area.width = row_area.width.saturating_sub(number_width);
+            }
+
             let item_style = self.style.patch(item.style);
             buf.set_style(row_area, item_style);
 
             let is_selected = state.selected.filter(|id| *id == item.id).is_some();
 
+            if self.pinned.contains(&item.id)
+                && let Some(span) = item
+                    .content
+                    .iter_mut()
+                    .last()
+                    .and_then(|line| line.spans.last_mut())
+            {
+                span.style = span.style.patch(self.pinned_style);
+            }
+
             if state.marked.contains(&item.id)
                 && let Some(span) = item
                     .content
@@ -60,21 +110,60 @@ impl StatefulWidget for Tree<'_> {
                 span.style = self.marked_style;
             }
 
-            if state.active.filter(|id| *id == item.id).is_some() {
+            if state.active.filter(|id| *id == item.id).is_some() && !self.active_marker.is_empty()
+            {
+                item.content
+                    .push_span(Span::styled(self.active_marker, self.active_style));
+            }
+
+            if self.identical.contains(&item.id) {
                 item.content
-                    .push_span(Span::styled(" (*)", self.active_style));
+                    .push_span(Span::styled(self.identical_marker, self.identical_style));
             }
 
-            item.content.render(row_area, buf);
+            (&item.content).render(row_area, buf);
 
             if is_selected {
-                buf.set_style(row_area, self.highlight_style);
+                self.highlight(&item, row_area, buf);
             }
         }
     }
 }
 
 impl Tree<'_> {
+    /// Highlights the row of the selected item. The spans of an item are the indent guides, the
+    /// fold indicator and the name, in that order.
+    fn highlight(&self, item: &TreeItem, row_area: Rect, buf: &mut Buffer) {
+        let Some(line) = item.content.lines.first() else {
+            return;
+        };
+
+        let width = |spans: &[Span]| spans.iter().map(|span| span.width() as u16).sum::<u16>();
+        let area = |start: usize, end: usize| {
+            let end = end.min(line.spans.len());
+            let start = start.min(end);
+
+            Rect {
+                x: row_area.x + width(&line.spans[..start]),
+                width: width(&line.spans[start..end]),
+                ..row_area
+            }
+            .intersection(row_area)
+        };
+
+        // the name is followed by the active save file or the identical save indicator, if any
+        let name_area = area(2, line.spans.len());
+
+        if self.name_only_highlight {
+            buf.set_style(name_area, self.highlight_style);
+        } else {
+            buf.set_style(row_area, self.highlight_style);
+        }
+
+        buf.set_style(area(1, 2), self.fold_highlight_style);
+        buf.set_style(name_area, self.name_highlight_style);
+    }
+
     fn get_items_bounds(
         &self,
         selected: Option<NodeId>,
@@ -111,9 +200,19 @@ impl Tree<'_> {
 
 #[cfg(test)]
 mod tests {
-    use crate::tree::{
-        NodeId,
-        widget::{item::TreeItem, state::TreeState, tree::Tree},
+    use crate::{
+        config::options::LineNumbers,
+        tree::{
+            NodeId,
+            widget::{item::TreeItem, state::TreeState, tree::Tree},
+        },
+    };
+    use ratatui::{
+        buffer::Buffer,
+        layout::Rect,
+        style::{Color, Style},
+        text::{Line, Span},
+        widgets::StatefulWidget,
     };
 
     #[test]
@@ -232,4 +331,58 @@ mod tests {
         assert_eq!(first, 1);
         assert_eq!(last, 2);
     }
+
+    #[test]
+    fn name_only_highlight() {
+        let items = vec![TreeItem {
+            content: Line::from(vec![Span::raw("  "), Span::raw("> "), Span::raw("name")]).into(),
+            style: Default::default(),
+            id: NodeId::new(1),
+        }];
+        let area = Rect::new(0, 0, 10, 1);
+        let mut state = TreeState::default();
+        state.select_unchecked(Some(NodeId::new(1)));
+
+        let mut buf = Buffer::empty(area);
+        Tree::new(items)
+            .highlight_style(Style::default().fg(Color::Red))
+            .name_and_fold_highlight_style(Style::default(), Style::default().fg(Color::Blue))
+            .name_only_highlight(true)
+            .render(area, &mut buf, &mut state);
+
+        let fg = |x| buf[(x, 0)].fg;
+
+        assert_eq!(fg(0), Color::Reset);
+        assert_eq!(fg(2), Color::Blue);
+        assert_eq!(fg(4), Color::Red);
+        assert_eq!(fg(7), Color::Red);
+        assert_eq!(fg(8), Color::Reset);
+    }
+
+    #[test]
+    fn relative_line_numbers() {
+        let items = (0..10).map(|idx| TreeItem {
+            content: "item".into(),
+            style: Default::default(),
+            id: NodeId::new(idx),
+        });
+        let area = Rect::new(0, 0, 10, 10);
+        let mut state = TreeState::default();
+        state.select_unchecked(Some(NodeId::new(2)));
+
+        let mut buf = Buffer::empty(area);
+        Tree::new(items)
+            .line_numbers(LineNumbers::Relative)
+            .render(area, &mut buf, &mut state);
+
+        let row = |y| {
+            (0..area.width)
+                .map(|x| buf[(x, y)].symbol())
+                .collect::<String>()
+        };
+
+        assert_eq!(row(0), " 2 item   ");
+        assert_eq!(row(2), " 3 item   ");
+        assert_eq!(row(9), " 7 item   ");
+    }
 }
@@ -1,5 +1,5 @@
 use crate::tree::{
-    NodeId, Tree,
+    Node, NodeId, Tree,
     traverse::{Traverse, next_start},
 };
 use std::collections::HashSet;
@@ -10,6 +10,10 @@ pub struct TreeState {
     pub offset: usize,
     pub marked: HashSet<NodeId>,
     pub active: Option<NodeId>,
+    /// Whether the root node is listed and can be selected like the other nodes.
+    pub root_visible: bool,
+    /// Whether the folders whose only child is a folder are shown on the line of the child.
+    pub compact_folders: bool,
 }
 
 impl TreeState {
@@ -56,7 +60,7 @@ impl TreeState {
     }
 
     /// Sets the node with the given `id` as the selected item. Aborts if trying to select the root
-    /// node.
+    /// node while it is not visible.
     ///
     /// Sets to `None` if no node is selected. Doesn't ensure that the ancestor nodes are expanded.
     ///
@@ -69,11 +73,93 @@ impl TreeState {
     /// state.select_unchecked(Some(NodeId::new(1)));
     /// ```
     pub fn select_unchecked(&mut self, id: Option<NodeId>) {
-        if matches!(id, Some(id) if id != NodeId::root()) {
+        if matches!(id, Some(id) if self.root_visible || id != NodeId::root()) {
             self.selected = id;
         }
     }
 
+    /// Returns the parent of the node if it can be selected, skipping the compacted ancestors.
+    pub fn parent_of<T>(&self, tree: &Tree<T>, id: NodeId) -> Option<NodeId> {
+        let parent = |node: &Node<T>| {
+            if self.root_visible {
+                node.parent()
+            } else {
+                node.non_root_parent()
+            }
+        };
+
+        let mut id = parent(&tree[id])?;
+
+        while self.is_compacted(tree, id) {
+            id = parent(&tree[id])?;
+        }
+
+        Some(id)
+    }
+
+    /// Returns `true` if the node is shown on the line of its only child, which is a folder.
+    /// Collapsed folders aren't compacted so that they can still be expanded on their own line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bawa::tree::Tree;
+    /// use bawa::tree::TreeState;
+    ///
+    /// let mut tree = Tree::default();
+    /// let r = tree.add_value("r");
+    /// let a = tree.add_value("a");
+    /// let a_b = tree.add_value("b");
+    /// let c = tree.add_value("c");
+    /// tree.append(r, a);
+    /// tree.append(a, a_b);
+    /// tree.append(r, c);
+    /// tree[a].expanded = Some(true);
+    /// tree[a_b].expanded = Some(false);
+    ///
+    /// let mut state = TreeState::default();
+    /// assert!(!state.is_compacted(&tree, a));
+    ///
+    /// state.compact_folders = true;
+    /// assert!(state.is_compacted(&tree, a));
+    ///
+    /// state.select_first(&tree);
+    /// assert_eq!(state.selected, Some(a_b));
+    /// state.select_next(&tree);
+    /// assert_eq!(state.selected, Some(c));
+    /// state.select_prev(&tree);
+    /// assert_eq!(state.selected, Some(a_b));
+    /// assert_eq!(state.parent_of(&tree, a_b), None);
+    ///
+    /// tree[a].toggle_fold();
+    /// assert!(!state.is_compacted(&tree, a));
+    /// ```
+    pub fn is_compacted<T>(&self, tree: &Tree<T>, id: NodeId) -> bool {
+        let node = &tree[id];
+
+        self.compact_folders
+            && id != NodeId::root()
+            && node.is_expanded()
+            && node.first_child == node.last_child
+            && (node.first_child).is_some_and(|child| tree[child].expanded.is_some())
+    }
+
+    /// Returns the node whose line the given node is shown on.
+    pub fn line_of<T>(&self, tree: &Tree<T>, mut id: NodeId) -> NodeId {
+        while self.is_compacted(tree, id) {
+            id = tree[id].first_child.unwrap();
+        }
+
+        id
+    }
+
+    /// Moves the selection from a compacted node to the line it is shown on.
+    pub fn select_line_of_selected<T>(&mut self, tree: &Tree<T>) {
+        if let Some(id) = self.selected.filter(|id| tree.get(*id).is_some()) {
+            self.selected = Some(self.line_of(tree, id));
+        }
+    }
+
     pub fn mark(&mut self, id: NodeId) -> bool {
         self.marked.insert(id)
     }
@@ -118,12 +204,12 @@ impl TreeState {
     /// ```
     pub fn select_next<T>(&mut self, tree: &Tree<T>) {
         if let Some(id) = self.selected {
-            self.selected = next_start(
-                &mut Traverse::new(NodeId::root(), tree)
-                    .visible()
-                    .from(id)
-                    .skip(1),
-            );
+            let mut edges = Traverse::new(NodeId::root(), tree)
+                .visible()
+                .from(id)
+                .skip(1);
+            self.selected = std::iter::from_fn(|| next_start(&mut edges))
+                .find(|id| !self.is_compacted(tree, *id));
         }
 
         if self.selected.is_none() {
@@ -169,13 +255,15 @@ impl TreeState {
     /// ```
     pub fn select_prev<T>(&mut self, tree: &Tree<T>) {
         if let Some(id) = self.selected {
-            self.selected = next_start(
-                &mut Traverse::new(NodeId::root(), tree)
-                    .visible()
-                    .to(id)
-                    .rev()
-                    .skip(1),
-            );
+            let mut edges = Traverse::new(NodeId::root(), tree)
+                .visible()
+                .to(id)
+                .rev()
+                .skip(1);
+            self.selected = std::iter::from_fn(|| next_start(&mut edges))
+                .find(|id| !self.is_compacted(tree, *id))
+                // the traversal doesn't yield the root
+                .or((self.root_visible && id != NodeId::root()).then_some(NodeId::root()));
         }
 
         if self.selected.is_none() {
@@ -183,7 +271,7 @@ impl TreeState {
         }
     }
 
-    /// Selects the first item of the tree.
+    /// Selects the first item of the tree, which is the root node if it is visible.
     ///
     /// # Examples
     ///
@@ -204,9 +292,20 @@ impl TreeState {
     /// state.select_first(&tree);
     ///
     /// assert_eq!(state.selected, Some(a));
+    ///
+    /// state.root_visible = true;
+    /// state.select_first(&tree);
+    ///
+    /// assert_eq!(state.selected, Some(r));
     /// ```
     pub fn select_first<T>(&mut self, tree: &Tree<T>) {
-        self.selected = tree.root().and_then(|node| node.first_child);
+        self.selected = tree.root().and_then(|node| {
+            if self.root_visible {
+                Some(NodeId::root())
+            } else {
+                node.first_child.map(|id| self.line_of(tree, id))
+            }
+        });
     }
 
     /// Selects the last visible item of the tree.
@@ -233,9 +332,22 @@ impl TreeState {
     /// state.select_last(&tree);
     ///
     /// assert_eq!(state.selected, Some(b_c));
+    ///
+    /// state.root_visible = true;
+    /// state.select_last(&tree);
+    ///
+    /// assert_eq!(state.selected, Some(r));
     /// ```
     pub fn select_last<T>(&mut self, tree: &Tree<T>) {
-        self.selected = std::iter::successors(tree.root().and_then(|node| node.last_child), |id| {
+        let start = tree.root().and_then(|node| {
+            if self.root_visible {
+                Some(NodeId::root())
+            } else {
+                node.last_child
+            }
+        });
+
+        self.selected = std::iter::successors(start, |id| {
             tree.get(*id)
                 .filter(|node| node.is_expanded())
                 .and_then(|node| node.last_child)
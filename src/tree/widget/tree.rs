@@ -1,10 +1,13 @@
-use super::item::TreeItem;
-use crate::tree::{
-    NodeId,
-    traverse::{Edge, Traverse},
+use super::{item::TreeItem, state::TreeState};
+use crate::{
+    config::options::LineNumbers,
+    tree::{
+        NodeId,
+        traverse::{Edge, Traverse},
+    },
 };
 use ratatui::{style::Style, widgets::Block};
-use std::fmt::Display;
+use std::{collections::HashSet, fmt::Display};
 
 #[derive(Default)]
 pub struct Tree<'a> {
@@ -12,8 +15,19 @@ pub struct Tree<'a> {
     pub items: Vec<TreeItem<'a>>,
     pub style: Style,
     pub highlight_style: Style,
+    pub name_highlight_style: Style,
+    pub fold_highlight_style: Style,
+    pub name_only_highlight: bool,
     pub marked_style: Style,
+    pub active_marker: &'a str,
     pub active_style: Style,
+    pub identical: HashSet<NodeId>,
+    pub identical_marker: &'a str,
+    pub identical_style: Style,
+    pub pinned: HashSet<NodeId>,
+    pub pinned_style: Style,
+    pub scrollbar: bool,
+    pub line_numbers: LineNumbers,
 }
 
 impl<'a> Tree<'a> {
@@ -37,38 +51,139 @@ impl<'a> Tree<'a> {
         self
     }
 
+    /// Sets the styles applied on top of the highlight style to the name and the fold indicator
+    /// of the selected item.
+    pub fn name_and_fold_highlight_style(mut self, name: Style, fold: Style) -> Self {
+        self.name_highlight_style = name;
+        self.fold_highlight_style = fold;
+        self
+    }
+
+    /// Highlights only the name of the selected item instead of its whole row.
+    pub fn name_only_highlight(mut self, name_only: bool) -> Self {
+        self.name_only_highlight = name_only;
+        self
+    }
+
     pub fn marked_style(mut self, style: Style) -> Self {
         self.marked_style = style;
         self
     }
 
-    pub fn active_style(mut self, style: Style) -> Self {
+    pub fn pinned(mut self, pinned: HashSet<NodeId>, style: Style) -> Self {
+        self.pinned = pinned;
+        self.pinned_style = style;
+        self
+    }
+
+    /// Sets the text appended to the active item and its style. Nothing is appended if `marker`
+    /// is empty.
+    pub fn active(mut self, marker: &'a str, style: Style) -> Self {
+        self.active_marker = marker;
         self.active_style = style;
         self
     }
-}
 
-impl<T> From<&crate::tree::Tree<T>> for Tree<'_>
-where
-    T: Display,
-{
-    fn from(tree: &crate::tree::Tree<T>) -> Self {
+    /// Sets the items identical to the active item, and the text appended to them and its style.
+    pub fn identical(mut self, identical: HashSet<NodeId>, marker: &'a str, style: Style) -> Self {
+        self.identical = identical;
+        self.identical_marker = marker;
+        self.identical_style = style;
+        self
+    }
+
+    /// Shows a scrollbar on the right edge if the items don't fit in the area.
+    pub fn scrollbar(mut self, scrollbar: bool) -> Self {
+        self.scrollbar = scrollbar;
+        self
+    }
+
+    /// Shows the line numbers of the items at their left.
+    pub fn line_numbers(mut self, line_numbers: LineNumbers) -> Self {
+        self.line_numbers = line_numbers;
+        self
+    }
+
+    /// Creates a widget that lists the given nodes without indentation.
+    pub fn flat<T>(tree: &crate::tree::Tree<T>, ids: impl IntoIterator<Item = NodeId>) -> Self
+    where
+        T: Display,
+    {
+        Tree::new(ids.into_iter().map(|id| TreeItem::new(id, 0, false, tree)))
+    }
+
+    /// Creates a widget that lists the visible nodes with indentation. The root node is listed
+    /// only if it is visible in the state. Compacted folders are prepended to the name of their
+    /// child instead of being listed.
+    pub fn nested<T>(tree: &crate::tree::Tree<T>, state: &TreeState) -> Self
+    where
+        T: Display,
+    {
         let mut items = Vec::new();
         let mut depth = 0;
+        // the first compacted folder of the line and the names of the compacted folders
+        let mut compacted: Option<(NodeId, String)> = None;
 
-        for edge in Traverse::new(NodeId::root(), tree).visible().skip(1) {
+        for edge in Traverse::new(NodeId::root(), tree)
+            .visible()
+            .skip(usize::from(!state.root_visible))
+        {
             match edge {
+                Edge::Start(id) if state.is_compacted(tree, id) => {
+                    let (_, prefix) = compacted.get_or_insert((id, String::new()));
+                    prefix.push_str(&format!("{}/", tree[id].value));
+                }
                 Edge::Start(id) => {
                     let node = &tree[id];
-                    let last_item =
-                        depth != 0 && node.next_sibling.is_none() && !node.has_children();
-                    items.push(TreeItem::new(id, depth, last_item, tree));
+                    let (first, prefix) = compacted.take().unzip();
+                    let last_item = depth != 0
+                        && tree[first.unwrap_or(id)].next_sibling.is_none()
+                        && !node.has_children();
+                    let item = TreeItem::new(id, depth, last_item, tree);
+                    items.push(match prefix {
+                        Some(prefix) => item.prefix_name(&prefix),
+                        None => item,
+                    });
                     depth += 1;
                 }
+                Edge::End(id) if state.is_compacted(tree, id) => (),
                 Edge::End(_) => depth -= 1,
             }
         }
 
         Tree::new(items)
     }
+
+    /// Creates a widget that lists the `shown` nodes with indentation, regardless of the fold
+    /// states. The root node is listed only if `show_root` is set.
+    pub fn filtered<T>(
+        tree: &crate::tree::Tree<T>,
+        shown: &HashSet<NodeId>,
+        show_root: bool,
+    ) -> Self
+    where
+        T: Display,
+    {
+        let depth_offset = usize::from(!show_root);
+        let items = (tree.descendants(NodeId::root()))
+            .filter(|id| shown.contains(id) || (show_root && *id == NodeId::root()))
+            .map(|id| {
+                let depth = tree.depth_of(id) - depth_offset;
+                let last_item = depth != 0
+                    && tree.following_siblings(id).all(|id| !shown.contains(&id))
+                    && tree.children(id).all(|id| !shown.contains(&id));
+                TreeItem::new(id, depth, last_item, tree)
+            });
+
+        Tree::new(items.collect::<Vec<_>>())
+    }
+}
+
+impl<T> From<&crate::tree::Tree<T>> for Tree<'_>
+where
+    T: Display,
+{
+    fn from(tree: &crate::tree::Tree<T>) -> Self {
+        Tree::nested(tree, &TreeState::default())
+    }
 }
@@ -1,5 +1,11 @@
-use super::{Scroller, popup::window_from_dimensions};
-use crate::{app::App, config::THEME};
+use super::{Scroller, popup::window_from_dimensions, set_cursor};
+use crate::{
+    app::App,
+    config::{OPTIONS, THEME},
+    input::Input,
+    tree::NodeId,
+    utils,
+};
 use ratatui::{
     Frame,
     layout::{Alignment, Constraint, Layout, Offset, Rect},
@@ -11,16 +17,29 @@ use ratatui::{
         ScrollbarState, StatefulWidget, Widget, Wrap,
     },
 };
+use std::path::PathBuf;
 
 pub fn draw_confirmation_window(f: &mut Frame, prompt: &mut Prompt) {
     let window = window_from_dimensions(20, 70, f.area());
-    f.render_widget(prompt, window);
+    f.render_widget(&mut *prompt, window);
+
+    if let Some(input) = &prompt.name_input {
+        let [_, input_area] = Layout::vertical([Constraint::Fill(1), Constraint::Length(1)])
+            .margin(1)
+            .areas(window);
+        set_cursor(f, input, input_area);
+    }
 }
 
 #[derive(Clone, Copy, Debug)]
 pub enum Context {
     Deletion,
     Replacing,
+    ReplacingWithActive,
+    BulkRename,
+    Pruning,
+    Move { top_level: bool },
+    ImportOverwrite,
     GameDeletion,
     ProfileDeletion,
 }
@@ -28,8 +47,13 @@ pub enum Context {
 pub struct Prompt {
     title: String,
     body: Vec<String>,
+    /// Shown above the body to point out a possible mistake.
+    warning: Option<String>,
     pub context: Context,
     pub scroller: Scroller,
+    /// Takes the name of the game or the profile to be deleted if `confirm_deletion_by_name` is
+    /// set, in place of the yes/no prompt.
+    pub name_input: Option<Input>,
 }
 
 impl Prompt {
@@ -41,24 +65,82 @@ impl Prompt {
                 } else {
                     (app.tree_state.marked.len(), "s")
                 };
-                format!("Permanently delete {count} selected file{postfix}")
+                let size = utils::format_size(app.total_size(&deleted_ids(app)));
+                format!("Permanently delete {count} selected file{postfix} ({size})")
+            }
+            Context::Replacing => "Overwrite the selected save".to_owned(),
+            Context::ReplacingWithActive => {
+                "Overwrite the selected save with the active save".to_owned()
             }
-            Context::Replacing => "Overwrite the selected file".to_owned(),
+            Context::BulkRename => format!("Rename {} marked entries", app.pending_renames.len()),
+            Context::Pruning => format!("Delete {} empty folders", app.pending_prunes.len()),
+            Context::Move { .. } => format!("Move {} marked entries", app.pending_moves.len()),
+            Context::ImportOverwrite => "Overwrite the existing file by importing".to_owned(),
             Context::GameDeletion => "Permanently delete the selected game".to_owned(),
             Context::ProfileDeletion => "Permanently delete the selected profile".to_owned(),
         };
 
         let body = match context {
-            Context::Deletion if !app.tree_state.marked.is_empty() => {
+            Context::Deletion => {
+                let profile = app.games.get_profile().unwrap();
+                deleted_ids(app)
+                    .into_iter()
+                    .map(|id| {
+                        let path =
+                            profile.display_path(&profile.entries[id].path, app.absolute_paths);
+                        let size = utils::format_size(app.total_size(&[id]));
+                        format!("{path} ({size})")
+                    })
+                    .collect()
+            }
+            Context::Replacing => {
                 let profile = app.games.get_profile().unwrap();
-                let marked_entries = app.tree_state.marked.iter();
-                marked_entries
-                    .map(|id| profile.rel_path_to(&profile.entries[*id].path))
+                vec![profile.display_path(&app.selected_entry().unwrap().path, app.absolute_paths)]
+            }
+            Context::ReplacingWithActive => {
+                let profile = app.games.get_profile().unwrap();
+                let active_path = profile.get_active_save_file().unwrap();
+                vec![format!(
+                    "{} -> {}",
+                    profile.display_path(&active_path, app.absolute_paths),
+                    profile.display_path(&app.selected_entry().unwrap().path, app.absolute_paths)
+                )]
+            }
+            Context::BulkRename => {
+                let profile = app.games.get_profile().unwrap();
+                app.pending_renames
+                    .iter()
+                    .map(|(from, to)| {
+                        format!(
+                            "{} -> {}",
+                            profile.display_path(from, app.absolute_paths),
+                            to.file_name().unwrap().to_string_lossy()
+                        )
+                    })
                     .collect()
             }
-            Context::Deletion | Context::Replacing => {
+            Context::Move { .. } => {
                 let profile = app.games.get_profile().unwrap();
-                vec![profile.rel_path_to(&app.selected_entry().unwrap().path)]
+                app.pending_moves
+                    .iter()
+                    .map(|(from, to)| {
+                        format!(
+                            "{} -> {}",
+                            profile.display_path(from, app.absolute_paths),
+                            profile.display_path(to, app.absolute_paths)
+                        )
+                    })
+                    .collect()
+            }
+            Context::Pruning => {
+                let profile = app.games.get_profile().unwrap();
+                (app.pending_prunes.iter())
+                    .map(|path| profile.display_path(path, app.absolute_paths))
+                    .collect()
+            }
+            Context::ImportOverwrite => {
+                let profile = app.games.get_profile().unwrap();
+                vec![profile.display_path(app.pending_import.as_ref().unwrap(), app.absolute_paths)]
             }
             Context::GameDeletion => {
                 vec![app.games.inner.get_selected().unwrap().name().into_owned()]
@@ -74,13 +156,57 @@ impl Prompt {
             }
         };
 
+        let warning = match context {
+            Context::Replacing if OPTIONS.get().warn_on_newer => {
+                let modified = |paths: &[PathBuf]| {
+                    (paths.iter())
+                        .filter_map(|path| path.metadata().and_then(|m| m.modified()).ok())
+                        .max()
+                };
+                let game = app.games.get_game_unchecked();
+                let selected = (app.selected_entry())
+                    .and_then(|entry| modified(&game.save_files_in(&entry.path)));
+                let live = modified(&game.savefile_paths);
+
+                selected
+                    .zip(live)
+                    .is_some_and(|(selected, live)| selected > live)
+                    .then(|| {
+                        "The selected file is newer than the save file of the game.".to_owned()
+                    })
+            }
+            _ => None,
+        };
+
+        let name_input = (OPTIONS.get().confirm_deletion_by_name
+            && matches!(context, Context::GameDeletion | Context::ProfileDeletion))
+        .then(|| Input::new("Type the name to confirm: "));
+
         Self {
             title,
             body,
+            warning,
             context,
             scroller: Scroller::default(),
+            name_input,
         }
     }
+
+    /// Returns true unless the prompt takes a name that doesn't match the one to be deleted.
+    pub fn name_matches(&self) -> bool {
+        self.name_input
+            .as_ref()
+            .is_none_or(|input| self.body.first() == Some(&input.text))
+    }
+}
+
+/// Returns the marked entries, or the selected one if none are marked.
+fn deleted_ids(app: &App) -> Vec<NodeId> {
+    if app.tree_state.marked.is_empty() {
+        app.tree_state.selected.into_iter().collect()
+    } else {
+        app.tree_state.marked.iter().copied().collect()
+    }
 }
 
 impl Widget for &mut Prompt {
@@ -91,9 +217,12 @@ impl Widget for &mut Prompt {
         Clear.render(area, buf);
 
         Block::bordered()
-            .title(Line::styled(self.title.clone(), THEME.confirmation_border))
+            .title(Line::styled(
+                self.title.clone(),
+                THEME.get().confirmation_border,
+            ))
             .border_type(BorderType::Rounded)
-            .border_style(THEME.confirmation_border)
+            .border_style(THEME.get().confirmation_border)
             .title_alignment(Alignment::Center)
             .render(area, buf);
 
@@ -103,14 +232,14 @@ impl Widget for &mut Prompt {
 
         let block = Block::default()
             .borders(Borders::BOTTOM)
-            .border_style(THEME.confirmation_border);
+            .border_style(THEME.get().confirmation_border);
         let inner_body_area = block.inner(body_area);
 
-        let body = self
-            .body
-            .iter()
-            .map(String::as_str)
-            .map(Line::from)
+        let warning =
+            (self.warning.as_deref()).map(|warning| Line::styled(warning, THEME.get().warning));
+        let body = warning
+            .into_iter()
+            .chain(self.body.iter().map(String::as_str).map(Line::from))
             .collect::<Vec<_>>();
 
         let offset = self.scroller.offset(inner_body_area, &body);
@@ -129,6 +258,15 @@ impl Widget for &mut Prompt {
             text = text.wrap(Wrap { trim: false });
         }
 
+        text.render(body_area, buf);
+
+        if let Some(input) = &mut self.name_input {
+            input.update_width(yes_no.width);
+            Line::from(vec![Span::raw(&input.prompt), Span::raw(input.to_string())])
+                .render(yes_no, buf);
+            return;
+        }
+
         let [yes_area, no_area] =
             Layout::horizontal([Constraint::Fill(1), Constraint::Fill(1)]).areas(yes_no);
 
@@ -143,7 +281,6 @@ impl Widget for &mut Prompt {
         ]))
         .centered();
 
-        text.render(body_area, buf);
         yes.render(yes_area, buf);
         no.render(no_area, buf);
     }
@@ -1,20 +1,30 @@
-use super::{confirmation::draw_confirmation_window, popup::window_from_dimensions, set_cursor};
+use super::{
+    confirmation::draw_confirmation_window,
+    popup::{window_from_dimensions, window_from_size},
+    set_cursor,
+};
 use crate::{
     app::{App, StatefulList},
-    config::THEME,
+    commands::Command,
+    config::{OPTIONS, THEME},
+    event_log::EventLog,
     fuzzy_finder::FuzzyFinder,
-    game::creation::{CreatingGame, Step},
+    game::{
+        SelectionItem,
+        creation::{CreatingGame, Step},
+    },
     help::Help,
     input::Mode,
     message::Kind as MessageKind,
-    tree::widget::Tree,
+    tree::{NodeId, widget::Tree},
+    utils,
 };
 use ratatui::{
     Frame,
-    layout::{Alignment, Constraint, Layout, Margin, Rect},
-    style::Style,
+    layout::{Alignment, Constraint, Flex, Layout, Margin, Rect},
+    style::{Color, Style},
     text::{Line, Span},
-    widgets::{Block, BorderType, Borders, Clear, List, ListItem, Paragraph, Wrap},
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
 };
 use std::fmt::Display;
 
@@ -31,14 +41,14 @@ pub fn draw(f: &mut Frame, app: &mut App) {
     draw_main(f, app, main_layout);
 
     if app.mode.is_profile_selection() {
-        draw_list_with_help(
+        draw_selection_list(
             f,
             format!("Profiles [{}]", app.games.get_game_unchecked().name()),
             app.games.get_profiles_mut(),
             &app.help.bindings.profile_selection,
         );
     } else if app.mode.is_game_selection() {
-        draw_list_with_help(
+        draw_selection_list(
             f,
             "Games".to_owned(),
             &mut app.games.inner,
@@ -51,23 +61,27 @@ pub fn draw(f: &mut Frame, app: &mut App) {
     }
 
     if app.fuzzy_finder.is_active() {
-        draw_fuzzy_finder(
-            f,
-            &mut app.fuzzy_finder,
-            window_from_dimensions(50, 90, f.area()),
-        );
+        let area = window_from_size(app.fuzzy_finder.size(), f.area());
+        draw_fuzzy_finder(f, &mut app.fuzzy_finder, area);
+    }
+
+    if let Mode::Confirmation(ref mut prompt) = app.mode {
+        draw_confirmation_window(f, prompt);
     }
 
+    // the help window can be opened in the confirmation window, so it is drawn above it
     if app.help.visible {
         draw_help(f, &mut app.help);
     }
 
-    if let Mode::Confirmation(ref mut prompt) = app.mode {
-        draw_confirmation_window(f, prompt);
+    if app.event_log.visible {
+        draw_event_log(f, &mut app.event_log);
     }
 }
 
 fn draw_game_creation(f: &mut Frame<'_>, state: &mut CreatingGame) {
+    let theme = THEME.get();
+
     match &mut state.step {
         Step::EnterName | Step::EnterPath => (),
         Step::PresetOrManual(use_preset) => {
@@ -77,10 +91,10 @@ fn draw_game_creation(f: &mut Frame<'_>, state: &mut CreatingGame) {
             let block = Block::bordered()
                 .title(Line::styled(
                     "Path Selection Method",
-                    THEME.confirmation_border,
+                    theme.confirmation_border,
                 ))
                 .border_type(BorderType::Rounded)
-                .border_style(THEME.confirmation_border)
+                .border_style(theme.confirmation_border)
                 .title_alignment(Alignment::Center);
 
             f.render_widget(&block, area);
@@ -94,14 +108,14 @@ fn draw_game_creation(f: &mut Frame<'_>, state: &mut CreatingGame) {
                 if *use_preset {
                     Style::default()
                 } else {
-                    THEME.confirmation_border
+                    theme.confirmation_border
                 },
             ))
             .centered();
             let preset = Paragraph::new(Line::styled(
                 "Preset",
                 if *use_preset {
-                    THEME.confirmation_border
+                    theme.confirmation_border
                 } else {
                     Style::default()
                 },
@@ -117,6 +131,9 @@ fn draw_game_creation(f: &mut Frame<'_>, state: &mut CreatingGame) {
 }
 
 fn draw_main(f: &mut Frame, app: &mut App, area: Rect) {
+    let theme = THEME.get();
+    let options = OPTIONS.get();
+
     let Some(game) = app.games.get_game() else {
         return;
     };
@@ -125,31 +142,122 @@ fn draw_main(f: &mut Frame, app: &mut App, area: Rect) {
         return;
     };
 
+    let breadcrumb = app.tree_state.selected.map_or(String::new(), |id| {
+        let entries = &profile.entries;
+
+        if app.absolute_paths {
+            return format!(" {} ", entries[id].path.display());
+        }
+
+        let mut names = entries
+            .ancestors(id)
+            .take_while(|id| *id != NodeId::root())
+            .map(|id| entries[id].name().to_string_lossy())
+            .collect::<Vec<_>>();
+        names.reverse();
+        names.push(entries[id].name().to_string_lossy());
+
+        format!(" {} ", names.join(" / "))
+    });
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .title(format!("{} [{}]", profile.name(), game.name()))
+        .title_style(theme.title)
+        .title_top(
+            Line::styled(
+                profile
+                    .note
+                    .as_ref()
+                    .map_or(String::new(), |note| format!(" {note} ")),
+                Color::DarkGray,
+            )
+            .right_aligned(),
+        )
+        .title_bottom(Line::styled(breadcrumb, Color::DarkGray));
+
+    if profile
+        .entries
+        .root()
+        .is_none_or(|root| !root.has_children())
+    {
+        draw_empty_profile_hint(f, &app.help, block, area);
+        return;
+    }
+
+    let tree = if app.flat_view {
+        Tree::flat(&profile.entries, app.visible_ids())
+    } else if let Some(shown) = &app.filter.shown {
+        Tree::filtered(&profile.entries, shown, app.tree_state.root_visible)
+    } else {
+        app.tree_state.select_line_of_selected(&profile.entries);
+        Tree::nested(&profile.entries, &app.tree_state)
+    };
+
     f.render_stateful_widget(
-        Tree::from(&profile.entries)
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .border_type(BorderType::Rounded)
-                    .title(format!("{} [{}]", profile.name(), game.name()))
-                    .title_style(THEME.title),
+        tree.block(block)
+            .highlight_style(theme.selected)
+            .name_and_fold_highlight_style(theme.selected_name, theme.selected_fold)
+            .name_only_highlight(!options.full_width_highlight)
+            .marked_style(theme.marked)
+            .pinned(profile.pinned_ids().clone(), theme.pinned)
+            .active(&options.active_marker, theme.active)
+            .identical(
+                app.identical.clone(),
+                &options.identical_marker,
+                theme.identical,
             )
-            .highlight_style(THEME.selected)
-            .marked_style(THEME.marked)
-            .active_style(THEME.active),
+            .scrollbar(options.scrollbar)
+            .line_numbers(options.line_numbers),
         area,
         &mut app.tree_state,
     );
 }
 
+/// Draws guidance in place of the tree of a profile without any entries.
+fn draw_empty_profile_hint(f: &mut Frame, help: &Help, block: Block, area: Rect) {
+    let key = |command: Command| {
+        let keys = help.bindings.general[command as usize].0.trim();
+        keys.split(", ").next().unwrap_or_default().to_owned()
+    };
+
+    let import = key(Command::ImportSaveFile);
+    let create_folder = key(Command::CreateFolder);
+
+    let guidance = match (import.is_empty(), create_folder.is_empty()) {
+        (false, false) => format!("Press {import} to import or {create_folder} to create a folder"),
+        (false, true) => format!("Press {import} to import a save file"),
+        (true, false) => format!("Press {create_folder} to create a folder"),
+        (true, true) => String::new(),
+    };
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let [hint_area] = Layout::vertical([Constraint::Length(2)])
+        .flex(Flex::Center)
+        .areas(inner);
+    let hint = Paragraph::new(vec![
+        Line::from("No save files yet"),
+        Line::styled(guidance, Color::DarkGray),
+    ])
+    .centered()
+    .wrap(Wrap { trim: true });
+
+    f.render_widget(hint, hint_area);
+}
+
 pub fn draw_fuzzy_finder(f: &mut Frame, fuzzy_finder: &mut FuzzyFinder, area: Rect) {
+    let theme = THEME.get();
+
     f.render_widget(Clear, area);
 
     let [mut search_bar_area, results_area] =
         Layout::vertical([Constraint::Length(3), Constraint::Fill(1)]).areas(area);
 
     let search_block = Block::default()
-        .title(Span::styled("Search", THEME.title))
+        .title(Span::styled("Search", theme.title))
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded);
 
@@ -163,12 +271,15 @@ pub fn draw_fuzzy_finder(f: &mut Frame, fuzzy_finder: &mut FuzzyFinder, area: Re
     ])
     .areas(search_bar_area);
 
-    let prompt = Paragraph::new(fuzzy_finder.input.prompt.clone()).style(THEME.fuzzy_prompt);
+    let prompt = Paragraph::new(fuzzy_finder.input.prompt.clone()).style(theme.fuzzy_prompt);
     f.render_widget(prompt, prompt_area);
 
     fuzzy_finder.input.update_width(search_bar_area.width);
 
-    let input = Paragraph::new(fuzzy_finder.input.to_string());
+    let mut input = Paragraph::new(fuzzy_finder.input.to_string());
+    if fuzzy_finder.input.selected {
+        input = input.style(Style::new().reversed());
+    }
     set_cursor(f, &fuzzy_finder.input, prompt_area);
     f.render_widget(input, input_area);
 
@@ -179,7 +290,11 @@ pub fn draw_fuzzy_finder(f: &mut Frame, fuzzy_finder: &mut FuzzyFinder, area: Re
     );
     if counter.len() <= counter_area.width.into() {
         let counter = Paragraph::new(counter)
-            .style(THEME.fuzzy_counter)
+            .style(if fuzzy_finder.match_count == 0 {
+                theme.fuzzy_counter_no_match
+            } else {
+                theme.fuzzy_counter
+            })
             .right_aligned();
         f.render_widget(counter, counter_area);
     }
@@ -198,9 +313,9 @@ pub fn draw_fuzzy_finder(f: &mut Frame, fuzzy_finder: &mut FuzzyFinder, area: Re
                     line.into_iter()
                         .map(|(slice, highlighted)| {
                             if highlighted {
-                                Span::styled(slice, THEME.highlight)
+                                Span::styled(slice, theme.highlight)
                             } else if selected_idx == idx {
-                                Span::styled(slice, THEME.fuzzy_selected)
+                                Span::styled(slice, theme.fuzzy_selected)
                             } else {
                                 Span::raw(slice)
                             }
@@ -212,7 +327,7 @@ pub fn draw_fuzzy_finder(f: &mut Frame, fuzzy_finder: &mut FuzzyFinder, area: Re
     )
     .block(
         Block::default()
-            .title(Span::styled("Results", THEME.title))
+            .title(Span::styled("Results", theme.title))
             .borders(Borders::ALL)
             .border_type(BorderType::Rounded),
     );
@@ -221,18 +336,30 @@ pub fn draw_fuzzy_finder(f: &mut Frame, fuzzy_finder: &mut FuzzyFinder, area: Re
 }
 
 fn draw_help(f: &mut Frame, help: &mut Help) {
-    let window = window_from_dimensions(45, 80, f.area());
+    let theme = THEME.get();
+
+    let window = window_from_size(help.size(), f.area());
     f.render_widget(Clear, window);
 
     let block = Block::default()
         .borders(Borders::ALL)
-        .title(Span::styled("Help", THEME.title));
+        .title(Span::styled("Help", theme.title));
 
-    let help_entries = help
-        .bindings
-        .iter()
-        .map(|(key, desc)| Line::from(vec![Span::styled(key, THEME.help), Span::raw(*desc)]))
-        .collect::<Vec<Line>>();
+    let entry = |(key, desc): (String, &'static str)| {
+        Line::from(vec![Span::styled(key, theme.help), desc.into()])
+    };
+
+    let section = help.bindings.section(help.section);
+    let mut help_entries = Vec::new();
+
+    if !section.is_empty() {
+        help_entries.push(Line::styled(help.section.title(), theme.title));
+        help_entries.extend(section.into_iter().map(entry));
+        help_entries.push(Line::default());
+        help_entries.push(Line::styled("General", theme.title));
+    }
+
+    help_entries.extend(help.bindings.iter().cloned().map(entry));
 
     let offset = help.scroller.offset(block.inner(window), &help_entries);
 
@@ -247,26 +374,109 @@ fn draw_help(f: &mut Frame, help: &mut Help) {
     f.render_widget(help_text, window);
 }
 
-fn draw_list_with_help<T: Display>(
+fn draw_event_log(f: &mut Frame, event_log: &mut EventLog) {
+    let theme = THEME.get();
+
+    let window = window_from_dimensions(60, 80, f.area());
+    f.render_widget(Clear, window);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(Span::styled("Event Log", theme.title));
+
+    let records = event_log
+        .records()
+        .map(|record| {
+            if record.failed {
+                Line::styled(record.text.clone(), theme.error)
+            } else {
+                Line::raw(record.text.clone())
+            }
+        })
+        .collect::<Vec<Line>>();
+
+    if records.is_empty() {
+        let hint = Paragraph::new(Line::styled("No events yet", Color::DarkGray)).block(block);
+        f.render_widget(hint, window);
+        return;
+    }
+
+    let offset = event_log.scroller.offset(block.inner(window), &records);
+
+    let mut text = Paragraph::new(records).scroll((offset, 0)).block(block);
+
+    if window.width > 0 {
+        text = text.wrap(Wrap { trim: false });
+    }
+
+    f.render_widget(text, window);
+}
+
+fn draw_selection_list<T: SelectionItem>(
     f: &mut Frame,
     title: String,
     list: &mut StatefulList<T>,
     help_entries: &[(String, &str)],
 ) {
-    const VER_MARGIN: u16 = 6;
-    const RIGHT_PADDING: u16 = 4;
+    let item_texts = list
+        .items
+        .iter()
+        .map(|item| {
+            let mut line = Line::raw(item.name().into_owned());
+
+            if let Some(note) = item.note() {
+                line.push_span(Span::styled(
+                    format!("  {note}"),
+                    Style::default().fg(Color::DarkGray).italic(),
+                ));
+            }
+
+            if let Some(timestamp) = item.last_used() {
+                line.push_span(Span::styled(
+                    format!("  {}", utils::format_time_since(timestamp)),
+                    Color::DarkGray,
+                ));
+            }
+
+            line
+        })
+        .collect();
 
-    let item_texts: Vec<Span> = list
+    draw_lines_with_help(f, title, item_texts, &mut list.state, help_entries);
+}
+
+fn draw_list_with_help<T: Display>(
+    f: &mut Frame,
+    title: String,
+    list: &mut StatefulList<T>,
+    help_entries: &[(String, &str)],
+) {
+    let item_texts = list
         .items
         .iter()
         .map(ToString::to_string)
-        .map(Span::raw)
+        .map(Line::raw)
         .collect();
 
+    draw_lines_with_help(f, title, item_texts, &mut list.state, help_entries);
+}
+
+fn draw_lines_with_help(
+    f: &mut Frame,
+    title: String,
+    item_texts: Vec<Line>,
+    state: &mut ListState,
+    help_entries: &[(String, &str)],
+) {
+    let theme = THEME.get();
+
+    const VER_MARGIN: u16 = 6;
+    const RIGHT_PADDING: u16 = 4;
+
     let mut spans = Vec::new();
 
     for entry in help_entries {
-        spans.push(Span::styled(entry.0.clone(), THEME.help));
+        spans.push(Span::styled(entry.0.clone(), theme.help));
         spans.push(Span::raw(entry.1));
     }
 
@@ -277,7 +487,7 @@ fn draw_list_with_help<T: Display>(
 
     let max_width = item_texts
         .iter()
-        .map(Span::width)
+        .map(Line::width)
         .max()
         .unwrap_or(0)
         .max(help_text_width) as u16
@@ -300,7 +510,7 @@ fn draw_list_with_help<T: Display>(
         Block::default()
             .borders(Borders::ALL)
             .title(title)
-            .title_style(THEME.title),
+            .title_style(theme.title),
         window,
     );
 
@@ -319,19 +529,26 @@ fn draw_list_with_help<T: Display>(
         .map(ListItem::new)
         .collect::<Vec<ListItem>>();
 
-    let w = List::new(list_items).highlight_style(THEME.selected);
+    let w = List::new(list_items).highlight_style(theme.selected);
 
-    f.render_stateful_widget(w, entry_area, &mut list.state);
+    f.render_stateful_widget(w, entry_area, state);
     f.render_widget(help_widget, help_area);
 }
 
 fn draw_footer(f: &mut Frame, app: &mut App, area: Rect) {
+    let theme = THEME.get();
+
     let line = if let Some(input) = &mut app.footer_input {
         set_cursor(f, input, area);
         input.update_width(area.width);
         let prompt = Span::raw(&input.prompt);
-        let text = if matches!(app.mode, Mode::Search(_)) && app.search.no_match() {
-            Span::styled(input.to_string(), THEME.error)
+        let no_match = match app.mode {
+            Mode::Search(_) => app.search.no_match(),
+            Mode::Filter => app.filter.no_match(),
+            _ => false,
+        };
+        let text = if no_match {
+            Span::styled(input.to_string(), theme.error)
         } else {
             Span::raw(input.to_string())
         };
@@ -341,8 +558,8 @@ fn draw_footer(f: &mut Frame, app: &mut App, area: Rect) {
             app.message.to_owned(),
             match app.message.kind {
                 MessageKind::Info => Style::default(),
-                MessageKind::Error => THEME.error,
-                MessageKind::Warning => THEME.warning,
+                MessageKind::Error => theme.error,
+                MessageKind::Warning => theme.warning,
             },
         ))
     } else {
@@ -350,4 +567,12 @@ fn draw_footer(f: &mut Frame, app: &mut App, area: Rect) {
     };
 
     f.render_widget(line, area);
+
+    if app.footer_input.is_some()
+        && matches!(app.mode, Mode::Search(_))
+        && let Some((current, total)) = app.search.counter()
+    {
+        let counter = Line::styled(format!("{current}/{total}"), theme.fuzzy_counter);
+        f.render_widget(counter.right_aligned(), area);
+    }
 }
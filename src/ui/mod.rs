@@ -1,4 +1,4 @@
-use crate::input::Input;
+use crate::{config::OPTIONS, input::Input};
 use crossterm::{
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
@@ -7,7 +7,11 @@ use ratatui::{
     DefaultTerminal, Frame, Terminal, TerminalOptions, Viewport, layout::Rect,
     prelude::CrosstermBackend,
 };
-use std::{io::stdout, panic, sync::Once};
+use std::{
+    io::{Write, stdout},
+    panic,
+    sync::Once,
+};
 
 pub use draw::{draw, draw_fuzzy_finder};
 pub use scroller::Scroller;
@@ -44,7 +48,10 @@ impl From<Options> for TerminalOptions {
 }
 
 pub fn init() -> DefaultTerminal {
-    init_with_options(Options::default())
+    init_with_options(Options {
+        alternate_screen: OPTIONS.get().alternate_screen,
+        ..Default::default()
+    })
 }
 
 pub fn init_inline(height: u16) -> DefaultTerminal {
@@ -81,6 +88,29 @@ fn set_panic_hook() {
     }));
 }
 
+/// Hands the terminal over to `f`, e.g. for running an external program, and sets it up again
+/// once `f` returns.
+pub fn suspend<T>(terminal: &mut DefaultTerminal, f: impl FnOnce() -> T) -> T {
+    restore();
+    let res = f();
+
+    enable_raw_mode().expect("Failed to enable raw mode.");
+
+    if ALTERNATE_SCREEN.is_completed() {
+        execute!(stdout(), EnterAlternateScreen).expect("Failed to enter alternate screen.");
+    }
+
+    terminal.clear().expect("Failed to clear terminal.");
+
+    res
+}
+
+/// Rings the terminal bell.
+pub fn bell() {
+    let mut stdout = stdout();
+    let _ = stdout.write_all(b"\x07").and_then(|()| stdout.flush());
+}
+
 pub fn restore() {
     disable_raw_mode().expect("Failed to disable raw mode.");
 
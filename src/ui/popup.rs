@@ -1,3 +1,4 @@
+use crate::config::options::PopupSize;
 use ratatui::layout::{Constraint, Flex, Layout, Rect};
 
 pub fn window_from_dimensions(height: u16, width: u16, r: Rect) -> Rect {
@@ -6,6 +7,10 @@ pub fn window_from_dimensions(height: u16, width: u16, r: Rect) -> Rect {
     window(&hor, &ver, r)
 }
 
+pub fn window_from_size(size: PopupSize, r: Rect) -> Rect {
+    window_from_dimensions(size.height, size.width, r)
+}
+
 fn _window_from_percentage(hor_percent: u16, ver_percent: u16, r: Rect) -> Rect {
     let ver = [Constraint::Percentage(ver_percent)];
     let hor = [Constraint::Percentage(hor_percent)];
@@ -1,12 +1,27 @@
+use crate::{cli::CLAP_ARGS, config::OPTIONS};
 use anyhow::{Result, bail};
+use jiff::{Timestamp, tz::TimeZone};
 use std::{
+    cmp::Ordering,
+    fmt::Display,
     fs,
-    io::Write,
+    io::{self, Write},
     path::{Path, PathBuf},
+    sync::LazyLock,
+    time::{SystemTime, UNIX_EPOCH},
 };
 
 const PACKAGE_NAME: &str = env!("CARGO_PKG_NAME");
 
+static NO_COLOR: LazyLock<bool> =
+    LazyLock::new(|| std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty()));
+
+/// Returns whether the output should be unstyled, which is the case when the `NO_COLOR`
+/// environment variable is set to a non-empty value.
+pub fn no_color() -> bool {
+    *NO_COLOR
+}
+
 pub fn get_state_dir() -> Result<PathBuf> {
     #[cfg(target_os = "linux")]
     let state_dir = dirs::state_dir().or_else(dirs::data_dir);
@@ -26,8 +41,13 @@ pub fn get_state_dir() -> Result<PathBuf> {
     Ok(path)
 }
 
+/// Returns the config directory, honoring `XDG_CONFIG_HOME` on every platform.
 pub fn get_config_dir() -> Result<PathBuf> {
-    let path = match dirs::config_dir() {
+    let xdg_config_home = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute());
+
+    let path = match xdg_config_home.or_else(dirs::config_dir) {
         Some(path) => path.join(PACKAGE_NAME),
         None => bail!("Couldn't find config directory"),
     };
@@ -59,14 +79,276 @@ pub fn check_for_dup(path: &Path) -> Result<()> {
 }
 
 pub fn rename(from: &Path, to: &Path) -> Result<()> {
-    check_for_dup(to)?;
-    Ok(fs::rename(from, to)?)
+    let res = check_for_dup(to).and_then(|()| Ok(fs::rename(from, to)?));
+    log("rename", &[from, to], &res);
+    res
+}
+
+/// Copies `from` to `to` recursively. `on_copy` is called after every copied file.
+pub fn copy_recursive(from: &Path, to: &Path, on_copy: &mut impl FnMut()) -> Result<()> {
+    if from.is_dir() {
+        fs::create_dir(to)?;
+
+        for entry in from.read_dir()? {
+            let entry = entry?;
+            copy_recursive(&entry.path(), &to.join(entry.file_name()), on_copy)?;
+        }
+    } else {
+        fs::copy(from, to)?;
+        on_copy();
+    }
+
+    Ok(())
+}
+
+/// Returns the number of files under `path`, or 1 if it is a file.
+pub fn count_files(path: &Path) -> usize {
+    if path.is_dir() {
+        path.read_dir().map_or(0, |entries| {
+            entries
+                .flatten()
+                .map(|entry| count_files(&entry.path()))
+                .sum()
+        })
+    } else {
+        1
+    }
+}
+
+/// Moves `from` to `to` by copying and then removing the source.
+pub fn copy_and_remove(from: &Path, to: &Path, on_copy: &mut impl FnMut()) -> Result<()> {
+    copy_recursive(from, to, on_copy)?;
+
+    Ok(if from.is_dir() {
+        fs::remove_dir_all(from)
+    } else {
+        fs::remove_file(from)
+    }?)
+}
+
+/// Returns `true` if the error is caused by trying to rename across file systems.
+pub fn crosses_devices(error: &io::Error) -> bool {
+    error.kind() == io::ErrorKind::CrossesDevices
+}
+
+/// Replaces `prefix` of `path` with `new_prefix`. Returns `None` if `path` doesn't start with
+/// `prefix`.
+pub fn replace_prefix(path: &Path, prefix: &Path, new_prefix: &Path) -> Option<PathBuf> {
+    let rel_path = path.strip_prefix(prefix).ok()?;
+
+    Some(if rel_path.as_os_str().is_empty() {
+        new_prefix.to_owned()
+    } else {
+        new_prefix.join(rel_path)
+    })
 }
 
 pub fn get_relative_path(base: &Path, path: &Path) -> Result<PathBuf> {
     Ok(path.strip_prefix(base)?.to_owned())
 }
 
+pub fn unix_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
+
+/// Returns the creation and the modification times of a file. The creation time falls back to
+/// the modification time on platforms and file systems that don't record it.
+pub fn file_times(metadata: &fs::Metadata) -> (Option<SystemTime>, Option<SystemTime>) {
+    let modified = metadata.modified().ok();
+    let created = metadata.created().ok().or(modified);
+
+    (created, modified)
+}
+
+/// Formats a number of bytes using binary prefixes.
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{size:.1} {}", UNITS[unit])
+}
+
+/// Formats the time elapsed since the given unix timestamp in a compact form.
+pub fn format_time_since(timestamp: u64) -> String {
+    let secs = unix_timestamp().saturating_sub(timestamp);
+
+    match secs {
+        0..60 => String::from("just now"),
+        60..3600 => format!("{}m ago", secs / 60),
+        3600..86400 => format!("{}h ago", secs / 3600),
+        _ => format!("{}d ago", secs / 86400),
+    }
+}
+
+/// Returns the current date as `YYYY-MM-DD` in the local time zone.
+pub fn current_date() -> String {
+    local_date_and_time().0
+}
+
+/// Returns the current time as `HH:MM:SS` in the local time zone.
+pub fn current_time() -> String {
+    local_date_and_time().1.replace('-', ":")
+}
+
+/// Returns the current date and time as `YYYY-MM-DD` and `HH-MM-SS` in the local time zone.
+fn local_date_and_time() -> (String, String) {
+    format_date_and_time(unix_timestamp(), &TimeZone::system())
+}
+
+/// Formats the given unix timestamp as `YYYY-MM-DD` and `HH-MM-SS` in `time_zone`.
+fn format_date_and_time(timestamp: u64, time_zone: &TimeZone) -> (String, String) {
+    let time = i64::try_from(timestamp)
+        .ok()
+        .and_then(|timestamp| Timestamp::from_second(timestamp).ok())
+        .unwrap_or_default()
+        .to_zoned(time_zone.clone());
+
+    (
+        time.strftime("%Y-%m-%d").to_string(),
+        time.strftime("%H-%M-%S").to_string(),
+    )
+}
+
+/// Splits a list of paths separated by the platform's separator (`:` on Unix, `;` on Windows),
+/// like the `PATH` variable.
+pub fn split_paths(paths: &str) -> Vec<PathBuf> {
+    std::env::split_paths(paths)
+        .filter(|path| !path.as_os_str().is_empty())
+        .collect()
+}
+
+pub fn join_paths(paths: &[PathBuf]) -> String {
+    std::env::join_paths(paths).map_or_else(
+        |_| {
+            (paths.iter())
+                .map(|path| path.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        },
+        |joined| joined.to_string_lossy().into_owned(),
+    )
+}
+
+/// Expands the placeholders of the `import_name_template` option and returns a path inside
+/// `dir`. The extension of `savefile_path` is appended to the name. The path can only exist if
+/// the template doesn't contain `{n}`.
+pub fn expand_import_name(template: &str, dir: &Path, game: &str, savefile_path: &Path) -> PathBuf {
+    let (date, time) = local_date_and_time();
+    let stem = savefile_path
+        .file_stem()
+        .map_or(String::new(), |stem| stem.to_string_lossy().into_owned());
+
+    let mut name = template
+        .replace("{datetime}", &format!("{date}_{time}"))
+        .replace("{date}", &date)
+        .replace("{game}", game)
+        .replace("{name}", &stem)
+        .replace(std::path::is_separator, "-");
+
+    if name.trim().is_empty() {
+        name = stem;
+    }
+
+    let with_ext = |name: String| match savefile_path.extension() {
+        Some(ext) => format!("{name}.{}", ext.to_string_lossy()),
+        None => name,
+    };
+
+    if name.contains("{n}") {
+        (1..)
+            .map(|n| dir.join(with_ext(name.replace("{n}", &n.to_string()))))
+            .find(|path| !path.exists())
+            .unwrap()
+    } else {
+        dir.join(with_ext(name))
+    }
+}
+
+/// Appends a line with the operation, the paths involved and its result to the log file in the
+/// state directory if logging is enabled with the `log` option or the `--log` flag.
+pub fn log<T, E: Display>(operation: &str, paths: &[&Path], res: &Result<T, E>) {
+    if !OPTIONS.get().log && !CLAP_ARGS.get_flag("log") {
+        return;
+    }
+
+    let Ok(log_path) = get_state_dir().map(|state_dir| state_dir.join(".log")) else {
+        return;
+    };
+
+    let (date, time) = local_date_and_time();
+    let paths = paths
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect::<Vec<_>>()
+        .join(" -> ");
+    let result = match res {
+        Ok(_) => String::from("ok"),
+        Err(e) => format!("error: {e}"),
+    };
+
+    let line = format!(
+        "{date} {} {operation} {paths}: {result}\n",
+        time.replace('-', ":")
+    );
+
+    let _ = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(log_path)
+        .and_then(|mut file| file.write_all(line.as_bytes()));
+}
+
+/// Compares the strings in human order, case-insensitively and treating the embedded numbers as
+/// numbers, so that "run2" comes before "run10".
+pub fn natural_cmp(a: &str, b: &str) -> Ordering {
+    fn take_number(chars: &mut std::iter::Peekable<std::str::Chars>) -> String {
+        let mut number = String::new();
+        while let Some(digit) = chars.next_if(char::is_ascii_digit) {
+            number.push(digit);
+        }
+        number.trim_start_matches('0').to_owned()
+    }
+
+    let (mut a_chars, mut b_chars) = (a.chars().peekable(), b.chars().peekable());
+
+    while let (Some(&a_char), Some(&b_char)) = (a_chars.peek(), b_chars.peek()) {
+        let ordering = if a_char.is_ascii_digit() && b_char.is_ascii_digit() {
+            let (a_number, b_number) = (take_number(&mut a_chars), take_number(&mut b_chars));
+            a_number
+                .len()
+                .cmp(&b_number.len())
+                .then_with(|| a_number.cmp(&b_number))
+        } else {
+            a_chars.next();
+            b_chars.next();
+            a_char.to_lowercase().cmp(b_char.to_lowercase())
+        };
+
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+
+    a_chars
+        .peek()
+        .is_some()
+        .cmp(&b_chars.peek().is_some())
+        .then_with(|| a.cmp(b))
+}
+
 pub fn write_atomic(path: &Path, content: &[u8]) -> Result<()> {
     let mut tmp = tempfile::Builder::new()
         .prefix(path.file_name().unwrap())
@@ -86,3 +368,69 @@ pub fn write_atomic(path: &Path, content: &[u8]) -> Result<()> {
     tmp.persist(path)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{expand_import_name, format_date_and_time, format_size, natural_cmp};
+    use jiff::tz::TimeZone;
+    use std::{cmp::Ordering, path::Path};
+
+    #[test]
+    fn date_and_time() {
+        assert_eq!(
+            format_date_and_time(0, &TimeZone::UTC),
+            (String::from("1970-01-01"), String::from("00-00-00"))
+        );
+        assert_eq!(
+            format_date_and_time(1_717_245_296, &TimeZone::UTC),
+            (String::from("2024-06-01"), String::from("12-34-56"))
+        );
+        assert_eq!(
+            format_date_and_time(951_782_400, &TimeZone::UTC),
+            (String::from("2000-02-29"), String::from("00-00-00"))
+        );
+    }
+
+    #[test]
+    fn size() {
+        assert_eq!(format_size(0), "0 B");
+        assert_eq!(format_size(1023), "1023 B");
+        assert_eq!(format_size(1536), "1.5 KiB");
+        assert_eq!(format_size(340 * 1024 * 1024), "340.0 MiB");
+        assert_eq!(format_size(3 * 1024 * 1024 * 1024), "3.0 GiB");
+    }
+
+    #[test]
+    fn import_name() {
+        let dir = tempfile::tempdir().unwrap();
+        let savefile = Path::new("/saves/ER0000.sl2");
+
+        let path = expand_import_name("{game}-{n}", dir.path(), "ER", savefile);
+        assert_eq!(path, dir.path().join("ER-1.sl2"));
+
+        std::fs::write(&path, "").unwrap();
+        let path = expand_import_name("{game}-{n}", dir.path(), "ER", savefile);
+        assert_eq!(path, dir.path().join("ER-2.sl2"));
+
+        let path = expand_import_name("{name}", dir.path(), "ER", savefile);
+        assert_eq!(path, dir.path().join("ER0000.sl2"));
+
+        let path = expand_import_name("a/b", dir.path(), "ER", savefile);
+        assert_eq!(path, dir.path().join("a-b.sl2"));
+    }
+
+    #[test]
+    fn natural_order() {
+        assert_eq!(natural_cmp("run2", "run10"), Ordering::Less);
+        assert_eq!(natural_cmp("run10", "run2"), Ordering::Greater);
+        assert_eq!(natural_cmp("Run2", "run10"), Ordering::Less);
+        assert_eq!(natural_cmp("run02", "run10"), Ordering::Less);
+        assert_eq!(natural_cmp("boss", "boss 2"), Ordering::Less);
+        assert_eq!(natural_cmp("a1b", "a1a"), Ordering::Greater);
+        assert_eq!(natural_cmp("save", "save"), Ordering::Equal);
+
+        let mut names = ["run10", "Run1", "run2", "boss", "run1b"];
+        names.sort_by(|a, b| natural_cmp(a, b));
+        assert_eq!(names, ["boss", "Run1", "run1b", "run2", "run10"]);
+    }
+}
@@ -5,13 +5,18 @@ use notify::{
     event::{CreateKind, Event as NotifyEvent, ModifyKind, RemoveKind, RenameMode},
 };
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::Duration,
 };
-use tokio::{sync::mpsc::UnboundedSender, task::JoinHandle};
+use tokio::{runtime::Handle as RuntimeHandle, sync::mpsc::UnboundedSender, task::JoinHandle};
 
 type Handle = (PathBuf, JoinHandle<()>);
 
+/// How long the writes to a file are gathered into one modification event.
+const MODIFY_DEBOUNCE: Duration = Duration::from_millis(200);
+
 pub struct Watcher {
     inner: RecommendedWatcher,
     pub handles: HashMap<Option<usize>, Handle>,
@@ -21,6 +26,8 @@ pub struct Watcher {
 impl Watcher {
     pub fn new(tx: UnboundedSender<Event>) -> Result<Self> {
         let tx_clone = tx.clone();
+        let runtime = RuntimeHandle::current();
+        let modified = Arc::new(Mutex::new(HashSet::new()));
 
         let watcher =
             notify::recommended_watcher(move |res: Result<notify::Event, notify::Error>| {
@@ -31,6 +38,7 @@ impl Watcher {
                 match event.kind {
                     EventKind::Create(_)
                     | EventKind::Remove(_)
+                    | EventKind::Modify(ModifyKind::Data(_))
                     | EventKind::Modify(ModifyKind::Name(RenameMode::From | RenameMode::To)) => (),
                     _ => return,
                 }
@@ -40,6 +48,24 @@ impl Watcher {
                     return;
                 }
 
+                // a write comes in many chunks, so only one event is sent for the writes to a file
+                // within the debounce interval
+                if let EventKind::Modify(ModifyKind::Data(_)) = event.kind {
+                    if !modified.lock().unwrap().insert(event.paths[0].clone()) {
+                        return;
+                    }
+
+                    let (tx, modified) = (tx_clone.clone(), Arc::clone(&modified));
+
+                    runtime.spawn(async move {
+                        tokio::time::sleep(MODIFY_DEBOUNCE).await;
+                        modified.lock().unwrap().remove(&event.paths[0]);
+                        tx.send(Event::FileSystem(event)).unwrap();
+                    });
+
+                    return;
+                }
+
                 tx_clone.send(Event::FileSystem(event)).unwrap();
             })?;
 
@@ -115,6 +141,7 @@ pub enum Context {
 pub enum Kind {
     Create,
     Rename(PathBuf),
+    Modify,
     Delete,
 }
 
@@ -122,6 +149,7 @@ impl From<NotifyEvent> for FileSystemEvent {
     fn from(value: NotifyEvent) -> Self {
         let kind = match value.kind {
             EventKind::Create(_) => Kind::Create,
+            EventKind::Modify(ModifyKind::Data(_)) => Kind::Modify,
             EventKind::Modify(_) => Kind::Rename(value.paths[1].clone()),
             EventKind::Remove(_) => Kind::Delete,
             _ => unreachable!(),
@@ -152,13 +180,26 @@ pub trait HandleFileSystemEvent {
     fn on_create(&mut self, path: &Path) -> Result<()>;
     fn on_rename(&mut self, path: &Path, new_path: &Path) -> Result<()>;
     fn on_delete(&mut self, path: &Path) -> Result<()>;
+    /// Called when the contents of the file at `path` change.
+    fn on_modify(&mut self, _path: &Path) -> Result<()> {
+        Ok(())
+    }
     fn handle_file_system_event(&mut self, event: &FileSystemEvent) -> Result<()> {
         let path = &event.path;
 
-        match event.kind {
-            Kind::Create => self.on_create(path),
-            Kind::Rename(ref new_path) => self.on_rename(path, new_path),
-            Kind::Delete => self.on_delete(path),
-        }
+        let (operation, paths, res) = match event.kind {
+            Kind::Create => ("watcher create", vec![path.as_path()], self.on_create(path)),
+            Kind::Rename(ref new_path) => (
+                "watcher rename",
+                vec![path.as_path(), new_path.as_path()],
+                self.on_rename(path, new_path),
+            ),
+            Kind::Delete => ("watcher delete", vec![path.as_path()], self.on_delete(path)),
+            // writes are too frequent to be logged
+            Kind::Modify => return self.on_modify(path),
+        };
+
+        utils::log(operation, &paths, &res);
+        res
     }
 }